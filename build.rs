@@ -8,15 +8,41 @@ fn main() -> std::io::Result<()> {
         .opt_level(3)
         .define("COMPACT", None)
         .define("NDEBUG", None)
-        .define("NOPTIONS", None)
         .define("NPROOFS", None)
         .define("QUIET", None);
+    if std::env::var_os("CARGO_FEATURE_OPTIONS").is_none() {
+        build.define("NOPTIONS", None);
+    }
 
     let version = std::fs::read_to_string("kissat/VERSION");
     let version = version.expect("missing kissat submodule");
+    println!("cargo:rustc-env=KISSAT_VERSION={}", version.trim());
     let version = format!("\"{}\"", version.trim());
     build.define("VERSION", version.as_ref());
 
+    // the submodule commit, which is not available in packaged sources;
+    // if kissat is not a checkout of its own, git would report the HEAD of
+    // an enclosing repository instead
+    let kissat = std::fs::canonicalize("kissat")?;
+    let output = std::process::Command::new("git")
+        .args(["-C", "kissat", "rev-parse", "--show-toplevel", "--absolute-git-dir", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    let id = output.as_deref().and_then(|output| {
+        let mut lines = output.lines();
+        let toplevel = std::fs::canonicalize(lines.next()?).ok()?;
+        let git_dir = lines.next()?;
+        let head = lines.next()?;
+        (toplevel == kissat).then(|| {
+            println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+            head
+        })
+    });
+    let id = format!("\"{}\"", id.unwrap_or("unknown"));
+    build.define("ID", id.as_ref());
+
     let files = vec![
         "kissat/src/allocate.c",
         "kissat/src/analyze.c",
@@ -97,6 +123,7 @@ fn main() -> std::io::Result<()> {
         "kissat/src/warmup.c",
         "kissat/src/watch.c",
         "kissat/src/weaken.c",
+        "src/shim.c",
     ];
 
     if build.get_compiler().is_like_msvc() {
@@ -0,0 +1,71 @@
+//! Solves a DIMACS formula read from stdin and writes the result in the SAT
+//! competition output format, with exit code 10 (SAT), 20 (UNSAT) or 0.
+//! This is the child process run by `cat_solver::solve_isolated`.
+//! Options: `--conflicts=N` and `--decisions=N` set the solve limits.
+
+use cat_solver::{Cnf, Limits, Solver};
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+
+fn parse_args() -> Result<Limits, String> {
+    let mut limits = Limits::default();
+    for arg in std::env::args().skip(1) {
+        let (limit, value) = if let Some(value) = arg.strip_prefix("--conflicts=") {
+            (&mut limits.conflicts, value)
+        } else if let Some(value) = arg.strip_prefix("--decisions=") {
+            (&mut limits.decisions, value)
+        } else {
+            return Err(format!("unknown argument {}", arg));
+        };
+        *limit = Some(value.parse().map_err(|_| format!("invalid limit {}", arg))?);
+    }
+    Ok(limits)
+}
+
+fn run(cnf: &Cnf, limits: &Limits) -> io::Result<u8> {
+    let mut sat = Solver::from(cnf);
+    sat.set_limits(limits);
+    let result = sat.solve();
+    let mut out = BufWriter::new(io::stdout().lock());
+    let code = match result {
+        Some(true) => {
+            writeln!(out, "s SATISFIABLE")?;
+            sat.write_model(&mut out)?;
+            10
+        }
+        Some(false) => {
+            writeln!(out, "s UNSATISFIABLE")?;
+            20
+        }
+        None => {
+            writeln!(out, "s UNKNOWN")?;
+            0
+        }
+    };
+    out.flush()?;
+    Ok(code)
+}
+
+fn main() -> ExitCode {
+    let limits = match parse_args() {
+        Ok(limits) => limits,
+        Err(err) => {
+            eprintln!("cat_solver_helper: {}", err);
+            return ExitCode::from(1);
+        }
+    };
+    let cnf = match Cnf::read_dimacs(io::stdin().lock()) {
+        Ok(cnf) => cnf,
+        Err(err) => {
+            eprintln!("cat_solver_helper: {}", err);
+            return ExitCode::from(1);
+        }
+    };
+    match run(&cnf, &limits) {
+        Ok(code) => ExitCode::from(code),
+        Err(err) => {
+            eprintln!("cat_solver_helper: {}", err);
+            ExitCode::from(1)
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{Model, Solver};
+
+/// A fixed-width unsigned integer encoded as literals, least significant
+/// bit first, for bit-level constraints such as in bounded model checking.
+/// The encoders Tseitin-encode their results with the gate helpers of
+/// `Solver`, introducing fresh auxiliary variables, and panic if the
+/// operands differ in width.
+/// # Examples
+/// ```
+/// let mut sat = cat_solver::Solver::new();
+/// let x = cat_solver::BitVec::new(&mut sat, 4);
+/// let y = cat_solver::BitVec::new(&mut sat, 4);
+/// let sum = x.encode_add(&mut sat, &y);
+/// let lt = sum.encode_ult(&mut sat, &x);
+/// sat.add_clause([lt]);
+/// assert_eq!(sat.solve(), Some(true));
+/// let model = sat.model();
+/// let x = x.value(&model).unwrap();
+/// let y = y.value(&model).unwrap();
+/// assert!(x + y >= 16);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitVec {
+    bits: Vec<i32>,
+}
+
+impl BitVec {
+    /// Returns a bit vector of `width` fresh unconstrained variables.
+    pub fn new(sat: &mut Solver, width: usize) -> Self {
+        BitVec {
+            bits: (0..width).map(|_| sat.new_var()).collect(),
+        }
+    }
+
+    /// Returns a bit vector over the given literals, least significant first.
+    pub fn from_lits(bits: Vec<i32>) -> Self {
+        debug_assert!(bits.iter().all(|&lit| crate::valid_lit(lit)));
+        BitVec { bits }
+    }
+
+    /// Returns the literals, least significant first.
+    pub fn bits(&self) -> &[i32] {
+        &self.bits
+    }
+
+    /// Returns the number of bits.
+    pub fn width(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Returns the value of the bit vector in `model`, or `None` if the
+    /// width exceeds 64 bits or a bit is a don't-care.
+    pub fn value(&self, model: &Model) -> Option<u64> {
+        if self.width() > 64 {
+            return None;
+        }
+        self.bits.iter().rev().try_fold(0, |value, &lit| Some(value << 1 | model.value(lit)? as u64))
+    }
+
+    /// Returns the sum of the two bit vectors modulo `2^width`, encoded by
+    /// a ripple-carry adder. The final carry is dropped.
+    pub fn encode_add(&self, sat: &mut Solver, other: &BitVec) -> BitVec {
+        self.prepare(sat, other);
+        let mut sum = Vec::with_capacity(self.width());
+        let mut carry: Option<i32> = None;
+        for (i, (&a, &b)) in self.bits.iter().zip(&other.bits).enumerate() {
+            let half = sat.xor_gate(a, b);
+            let last = i + 1 == self.width();
+            match carry {
+                None => {
+                    sum.push(half);
+                    if !last {
+                        carry = Some(sat.and_gate(&[a, b]));
+                    }
+                }
+                Some(c) => {
+                    sum.push(sat.xor_gate(half, c));
+                    if !last {
+                        let generate = sat.and_gate(&[a, b]);
+                        let propagate = sat.and_gate(&[half, c]);
+                        carry = Some(sat.or_gate(&[generate, propagate]));
+                    }
+                }
+            }
+        }
+        BitVec { bits: sum }
+    }
+
+    /// Returns a literal which is true if and only if the two bit vectors
+    /// are equal. It is true for width 0.
+    pub fn encode_eq(&self, sat: &mut Solver, other: &BitVec) -> i32 {
+        self.prepare(sat, other);
+        let same: Vec<i32> = self.bits.iter().zip(&other.bits).map(|(&a, &b)| -sat.xor_gate(a, b)).collect();
+        sat.and_gate(&same)
+    }
+
+    /// Returns a literal which is true if and only if this bit vector is
+    /// less than `other` as an unsigned integer, encoded by a comparator
+    /// chain from the least significant bit up. It is false for width 0.
+    pub fn encode_ult(&self, sat: &mut Solver, other: &BitVec) -> i32 {
+        self.prepare(sat, other);
+        // whether the low bits seen so far compare less
+        let mut lt = sat.or_gate(&[]);
+        for (&a, &b) in self.bits.iter().zip(&other.bits) {
+            let less = sat.and_gate(&[-a, b]);
+            let same = -sat.xor_gate(a, b);
+            let below = sat.and_gate(&[same, lt]);
+            lt = sat.or_gate(&[less, below]);
+        }
+        lt
+    }
+
+    /// Checks the widths and reserves the bits of both operands, so that the
+    /// fresh variables of an encoding cannot alias bits the solver has not
+    /// seen yet.
+    fn prepare(&self, sat: &mut Solver, other: &BitVec) {
+        assert_eq!(self.width(), other.width(), "bit vectors differ in width");
+        sat.reserve_lits(&self.bits);
+        sat.reserve_lits(&other.bits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: usize = 3;
+
+    #[test]
+    fn exhaustive() {
+        let mut sat = Solver::new();
+        let x = BitVec::new(&mut sat, WIDTH);
+        let y = BitVec::new(&mut sat, WIDTH);
+        let sum = x.encode_add(&mut sat, &y);
+        let eq = x.encode_eq(&mut sat, &y);
+        let lt = x.encode_ult(&mut sat, &y);
+        for a in 0..1u64 << WIDTH {
+            for b in 0..1u64 << WIDTH {
+                for (i, (&xi, &yi)) in x.bits().iter().zip(y.bits()).enumerate() {
+                    sat.assume(if a >> i & 1 == 1 { xi } else { -xi });
+                    sat.assume(if b >> i & 1 == 1 { yi } else { -yi });
+                }
+                assert_eq!(sat.solve(), Some(true));
+                let model = sat.model();
+                assert_eq!(sum.value(&model), Some((a + b) % (1 << WIDTH)));
+                assert_eq!(model.value(eq), Some(a == b));
+                assert_eq!(model.value(lt), Some(a < b));
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let mut sat = Solver::new();
+        let x = BitVec::new(&mut sat, 0);
+        let eq = x.encode_eq(&mut sat, &x);
+        let lt = x.encode_ult(&mut sat, &x);
+        assert_eq!(x.encode_add(&mut sat, &x).width(), 0);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!((sat.value(eq), sat.value(lt)), (Some(true), Some(false)));
+    }
+
+    #[test]
+    fn from_lits() {
+        let mut sat = Solver::new();
+        let x = BitVec::from_lits(vec![1, 2]);
+        let y = BitVec::from_lits(vec![3, -4]);
+        let sum = x.encode_add(&mut sat, &y);
+        let lt = x.encode_ult(&mut sat, &y);
+        assert!(sum.bits().iter().chain([&lt]).all(|lit| lit.abs() > 4));
+        for lit in [1, -2, 3, 4] {
+            sat.assume(lit);
+        }
+        assert_eq!(sat.solve(), Some(true));
+        let model = sat.model();
+        assert_eq!((sum.value(&model), model.value(lt)), (Some(2), Some(false)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn width() {
+        let mut sat = Solver::new();
+        let x = BitVec::new(&mut sat, 2);
+        let y = BitVec::new(&mut sat, 3);
+        x.encode_eq(&mut sat, &y);
+    }
+}
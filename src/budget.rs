@@ -0,0 +1,137 @@
+use crate::{Solver, Unknown};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A time budget shared by several solvers. Clones refer to the same
+/// budget: every solve started with `Solver::solve_within` draws from it
+/// while it runs, and once it is used up, all of them stop.
+#[derive(Clone, Debug)]
+pub struct Budget {
+    nanos: Arc<AtomicU64>,
+}
+
+impl Budget {
+    /// Creates a budget of the given duration.
+    pub fn new(time: Duration) -> Self {
+        let nanos = u64::try_from(time.as_nanos()).unwrap_or(u64::MAX);
+        Budget {
+            nanos: Arc::new(AtomicU64::new(nanos)),
+        }
+    }
+
+    /// Returns the time left in the budget.
+    pub fn remaining(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed))
+    }
+
+    /// Returns whether the budget is used up.
+    pub fn is_exhausted(&self) -> bool {
+        self.nanos.load(Ordering::Relaxed) == 0
+    }
+
+    /// Takes `nanos` from the budget, and returns whether it is used up.
+    fn spend(&self, nanos: u64) -> bool {
+        let prev = self.nanos.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| Some(left.saturating_sub(nanos)));
+        prev.unwrap_or_else(|left| left) <= nanos
+    }
+}
+
+impl Solver {
+    /// Solves like `solve`, charging the wall-clock time of the solve to
+    /// `budget`, and terminating once it is used up, whether by this solve
+    /// or by others sharing it. The time is charged whenever Kissat polls
+    /// for termination, which it does frequently, so the time after the
+    /// last poll is not charged.
+    pub fn solve_within(&mut self, budget: &Budget) -> Option<bool> {
+        let budget = budget.clone();
+        let mut last = Instant::now();
+        let terminate = Box::new(move || {
+            let now = Instant::now();
+            let nanos = u64::try_from((now - last).as_nanos()).unwrap_or(u64::MAX);
+            last = now;
+            budget.spend(nanos)
+        });
+        self.solve_scoped(terminate).ok()
+    }
+
+    /// Solves like `solve`, but gives up once `timeout` has passed,
+    /// returning `Err(SolveError::Timeout)`. Other reasons for an undecided
+    /// solve are reported separately: `SolveError::Limit` for a limit set
+    /// with `set_limit` or `set_memory_limit`, and `SolveError::Interrupted`
+    /// for a terminate callback or Ctrl-C. The deadline is checked whenever
+    /// Kissat polls for termination, so it may be overrun slightly.
+    pub fn solve_timeout(&mut self, timeout: Duration) -> Result<bool, SolveError> {
+        let deadline = Instant::now().checked_add(timeout);
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let flag = timed_out.clone();
+        let terminate = Box::new(move || {
+            let expired = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+            if expired {
+                flag.store(true, Ordering::Relaxed);
+            }
+            expired
+        });
+        match self.solve_scoped(terminate) {
+            Ok(result) => Ok(result),
+            Err(_) if timed_out.load(Ordering::Relaxed) => Err(SolveError::Timeout),
+            Err(Unknown::Limit) => Err(SolveError::Limit),
+            Err(Unknown::Terminated) => Err(SolveError::Interrupted),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The reason `Solver::solve_timeout` ended without deciding the formula.
+pub enum SolveError {
+    /// The timeout passed.
+    Timeout,
+    /// A conflict, decision or memory limit was reached.
+    Limit,
+    /// The solve was terminated by a callback or Ctrl-C.
+    Interrupted,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Timeout => "timed out".fmt(f),
+            SolveError::Limit => "limit reached".fmt(f),
+            SolveError::Interrupted => "interrupted".fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_timeout() {
+        let mut sat = crate::tests::pigeon_hole(12);
+        assert_eq!(sat.solve_timeout(Duration::from_millis(50)), Err(SolveError::Timeout));
+        sat.set_terminate(|| true);
+        assert_eq!(sat.solve_timeout(Duration::from_secs(60)), Err(SolveError::Interrupted));
+        sat.clear_terminate();
+        sat.set_limit("conflicts", 10).unwrap();
+        assert_eq!(sat.solve_timeout(Duration::from_secs(60)), Err(SolveError::Limit));
+        let mut sat = crate::tests::pigeon_hole(3);
+        assert_eq!(sat.solve_timeout(Duration::MAX), Ok(false));
+    }
+
+    #[test]
+    fn solve_within() {
+        let budget = Budget::new(Duration::ZERO);
+        assert!(budget.is_exhausted());
+        let mut sat = crate::tests::pigeon_hole(9);
+        assert_eq!(sat.solve_within(&budget), None);
+
+        let budget = Budget::new(Duration::from_secs(60));
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.add_clause([-1]);
+        assert_eq!(sat.solve_within(&budget), Some(true));
+        assert!(budget.remaining() <= Duration::from_secs(60));
+    }
+}
@@ -1,5 +1,7 @@
 #define VERSION "3.1.1"
 #define COMPILER "unknown"  
+#ifndef ID
 #define ID "unknown"
+#endif
 #define BUILD "unknown"
 #define DIR "unknown"
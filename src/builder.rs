@@ -0,0 +1,249 @@
+use crate::{Cnf, Error, Limits, Outcome, Solver};
+
+/// An inprocessing technique of Kissat, which `SolverBuilder::disable`
+/// can switch off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Technique {
+    /// Clause vivification, option `vivify`.
+    Vivify,
+    /// Bounded variable elimination, option `eliminate`.
+    Eliminate,
+    /// Probing, option `probe`.
+    Probe,
+    /// Equivalent literal substitution, option `substitute`.
+    Substitute,
+    /// SAT sweeping, option `sweep`.
+    Sweep,
+    /// Backbone computation, option `backbone`.
+    Backbone,
+}
+
+impl Technique {
+    /// Returns the name of the Kissat option enabling the technique.
+    pub fn option_name(self) -> &'static str {
+        match self {
+            Technique::Vivify => "vivify",
+            Technique::Eliminate => "eliminate",
+            Technique::Probe => "probe",
+            Technique::Substitute => "substitute",
+            Technique::Sweep => "sweep",
+            Technique::Backbone => "backbone",
+        }
+    }
+}
+
+/// A builder for solvers with a configuration and runtime options. The
+/// settings are only checked by `build`, which can be called repeatedly.
+/// # Examples
+/// ```
+/// use cat_solver::{SolverBuilder, Technique};
+/// let mut builder = SolverBuilder::new();
+/// builder.config("unsat").disable(Technique::Vivify);
+/// // options only take effect with the `options` feature
+/// assert_eq!(builder.build().is_ok(), cfg!(feature = "options"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SolverBuilder {
+    max_var: i32,
+    config: Option<String>,
+    options: Vec<(String, i32)>,
+}
+
+impl SolverBuilder {
+    /// Creates a builder for a solver with the default settings.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Reserves variables up to `max_var`, as `Solver::reserve` does.
+    pub fn max_var(&mut self, max_var: i32) -> &mut Self {
+        self.max_var = max_var;
+        self
+    }
+
+    /// Applies one of the configurations listed for `Solver::with_config`.
+    pub fn config(&mut self, name: &str) -> &mut Self {
+        self.config = Some(name.to_string());
+        self
+    }
+
+    /// Sets a runtime option, as `Solver::set_option` does. Options are
+    /// applied in order, after the configuration.
+    pub fn option(&mut self, name: &str, value: i32) -> &mut Self {
+        self.options.push((name.to_string(), value));
+        self
+    }
+
+    /// Disables an inprocessing technique, by setting its option to 0.
+    pub fn disable(&mut self, technique: Technique) -> &mut Self {
+        self.option(technique.option_name(), 0)
+    }
+
+    /// Creates a solver with the given settings. Fails, naming the setting,
+    /// if the configuration is unknown or an option is rejected; without
+    /// the `options` feature, only default option values are accepted.
+    pub fn build(&self) -> Result<Solver, Error> {
+        let mut sat = match &self.config {
+            Some(config) => Solver::with_config(self.max_var, config)?,
+            None => {
+                let mut sat = Solver::new();
+                if self.max_var > 0 {
+                    sat.reserve(self.max_var);
+                }
+                sat
+            }
+        };
+        let options: Vec<(&str, i32)> = self.options.iter().map(|(name, value)| (name.as_str(), *value)).collect();
+        sat.set_options(&options)?;
+        Ok(sat)
+    }
+
+    /// Checks the settings once and returns a factory for solvers which
+    /// share them, so that a batch of instances is solved consistently.
+    pub fn factory(&self) -> Result<SolverFactory, Error> {
+        self.build()?;
+        Ok(SolverFactory { builder: self.clone() })
+    }
+}
+
+/// Creates solvers with the settings of a `SolverBuilder`, which were
+/// checked when the factory was made by `SolverBuilder::factory`.
+/// # Examples
+/// ```
+/// let factory = cat_solver::SolverBuilder::new().config("plain").factory().unwrap();
+/// for clause in [[1, 2], [-1, -2]] {
+///     let mut sat = factory.create();
+///     sat.add_clause(clause);
+///     assert_eq!(sat.solve(), Some(true));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SolverFactory {
+    builder: SolverBuilder,
+}
+
+impl SolverFactory {
+    /// Creates a fresh solver with the factory's settings.
+    pub fn create(&self) -> Solver {
+        self.builder.build().expect("settings were checked by SolverBuilder::factory")
+    }
+
+    /// Returns the settings of the factory.
+    pub fn builder(&self) -> &SolverBuilder {
+        &self.builder
+    }
+}
+
+/// The settings of one solve of a `Cnf` with `Cnf::solve_with`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolverConfig {
+    /// One of the configurations listed for `Solver::with_config`.
+    pub configuration: Option<String>,
+    /// Runtime options, applied in order after the configuration.
+    pub options: Vec<(String, i32)>,
+    /// Limits for the solve.
+    pub limits: Limits,
+}
+
+impl Cnf {
+    /// Solves the formula on a fresh solver with the given settings,
+    /// leaving the formula untouched, so that one parsed formula can be
+    /// solved under many configurations, like `solve_isolated` but in this
+    /// process. Each call still copies the clauses into the new solver.
+    /// Fails if the configuration is unknown or an option is rejected, as
+    /// `SolverBuilder::build` does.
+    pub fn solve_with(&self, config: &SolverConfig) -> Result<Outcome, Error> {
+        let mut builder = SolverBuilder::new();
+        builder.max_var(self.num_vars());
+        if let Some(name) = &config.configuration {
+            builder.config(name);
+        }
+        for (name, value) in &config.options {
+            builder.option(name, *value);
+        }
+        let mut sat = builder.build()?;
+        for clause in self.clauses() {
+            sat.add_clause(clause.iter().copied());
+        }
+        sat.set_limits(&config.limits);
+        Ok(match sat.solve() {
+            Some(true) => Outcome::Sat(sat.model()),
+            Some(false) => Outcome::Unsat,
+            None => Outcome::Unknown,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build() {
+        let mut sat = SolverBuilder::new().max_var(3).config("plain").build().unwrap();
+        assert_eq!(sat.num_vars(), 3);
+        sat.add_clause([1, 3]);
+        assert_eq!(sat.solve(), Some(true));
+        assert!(SolverBuilder::new().config("bad").build().is_err());
+        assert_eq!(SolverBuilder::new().config("plain").build().unwrap().num_vars(), 0);
+    }
+
+    #[test]
+    fn factory() {
+        let factory = SolverBuilder::new().max_var(2).factory().unwrap();
+        let mut a = factory.create();
+        let mut b = factory.create();
+        a.add_clause([1]);
+        assert_eq!((a.num_vars(), b.num_vars()), (2, 2));
+        assert_eq!((a.solve(), b.solve()), (Some(true), Some(true)));
+        assert!(SolverBuilder::new().config("bad").factory().is_err());
+    }
+
+    #[test]
+    fn solve_with() {
+        let mut cnf = Cnf::new();
+        cnf.add_clause([1, 2]);
+        cnf.add_clause([-1]);
+        let model = match cnf.solve_with(&SolverConfig::default()).unwrap() {
+            Outcome::Sat(model) => model,
+            outcome => panic!("unexpected {:?}", outcome),
+        };
+        assert_eq!(model.value(2), Some(true));
+        let config = SolverConfig {
+            configuration: Some("plain".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(cnf.solve_with(&config), Ok(Outcome::Sat(_))));
+        assert!(matches!(Cnf::new().solve_with(&config), Ok(Outcome::Sat(_))));
+        let config = SolverConfig {
+            configuration: Some("bad".to_string()),
+            ..Default::default()
+        };
+        assert!(cnf.solve_with(&config).is_err());
+        let pigeons = crate::tests::pigeon_hole(9).cnf().clone();
+        let config = SolverConfig {
+            limits: Limits {
+                conflicts: Some(100),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(pigeons.solve_with(&config), Ok(Outcome::Unknown));
+        let pigeons = crate::tests::pigeon_hole(3).cnf().clone();
+        assert_eq!(pigeons.solve_with(&config), Ok(Outcome::Unsat));
+    }
+
+    #[test]
+    fn disable() {
+        let mut builder = SolverBuilder::new();
+        builder.disable(Technique::Eliminate).disable(Technique::Sweep);
+        let result = builder.build();
+        if cfg!(feature = "options") {
+            let mut sat = result.unwrap();
+            sat.add_clause([1]);
+            assert_eq!(sat.solve(), Some(true));
+        } else {
+            assert_eq!(result.err(), Some(Error::new("cannot set option 'eliminate' to 0")));
+        }
+    }
+}
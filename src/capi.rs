@@ -0,0 +1,143 @@
+//! A C ABI over `Solver`, in the style of IPASIR, for hosts which load the
+//! solver as a shared library. Build it with
+//! `cargo rustc --release --features capi --crate-type cdylib`.
+//!
+//! A solver is an opaque pointer returned by `cat_solver_init`, which must
+//! be passed to `cat_solver_release` exactly once. A solver may be moved
+//! between threads, but not used from two threads at once. Literals are
+//! non-zero DIMACS integers other than `INT_MIN`. Clauses are added one
+//! literal at a time and terminated by 0; adding a clause after a solve
+//! resets the solver, as `IncrementalSat::add_clause` does. A panic inside
+//! these functions aborts the process.
+
+use crate::{IncrementalSat, Solver};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_uint};
+
+/// The state behind a `cat_solver_*` pointer.
+pub struct CatSolver {
+    sat: Solver,
+    clause: Vec<i32>,
+}
+
+/// Returns the name and version of the Kissat library, as a static string.
+#[no_mangle]
+pub extern "C" fn cat_solver_signature() -> *const c_char {
+    unsafe { crate::kissat_signature() }
+}
+
+/// Creates a new solver.
+#[no_mangle]
+pub extern "C" fn cat_solver_init() -> *mut CatSolver {
+    let sat = CatSolver {
+        sat: Solver::new(),
+        clause: Vec::new(),
+    };
+    Box::into_raw(Box::new(sat))
+}
+
+/// Releases a solver.
+/// # Safety
+/// `ptr` must come from `cat_solver_init` and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn cat_solver_release(ptr: *mut CatSolver) {
+    drop(Box::from_raw(ptr));
+}
+
+/// Adds a literal to the current clause, or terminates it if `lit` is 0.
+/// # Safety
+/// `ptr` must be a live solver from `cat_solver_init`.
+#[no_mangle]
+pub unsafe extern "C" fn cat_solver_add(ptr: *mut CatSolver, lit: c_int) {
+    let state = &mut *ptr;
+    if lit == 0 {
+        IncrementalSat::add_clause(&mut state.sat, &state.clause);
+        state.clause.clear();
+    } else {
+        state.clause.push(lit);
+    }
+}
+
+/// Assumes a literal for the next solve only.
+/// # Safety
+/// `ptr` must be a live solver from `cat_solver_init`.
+#[no_mangle]
+pub unsafe extern "C" fn cat_solver_assume(ptr: *mut CatSolver, lit: c_int) {
+    (*ptr).sat.assume(lit);
+}
+
+/// Sets the `conflicts` or `decisions` limit for the next solve, as
+/// `Solver::set_limit` does. Returns 0 on success and -1 for an unknown
+/// or invalid name.
+/// # Safety
+/// `ptr` must be a live solver from `cat_solver_init`, and `name` a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn cat_solver_set_limit(ptr: *mut CatSolver, name: *const c_char, limit: c_uint) -> c_int {
+    match CStr::from_ptr(name).to_str() {
+        Ok(name) if (*ptr).sat.set_limit(name, limit).is_ok() => 0,
+        _ => -1,
+    }
+}
+
+/// Solves the formula, returning 10 if it is satisfiable, 20 if it is
+/// unsatisfiable and 0 if the solve was undecided.
+/// # Safety
+/// `ptr` must be a live solver from `cat_solver_init`, with no clause
+/// left unterminated.
+#[no_mangle]
+pub unsafe extern "C" fn cat_solver_solve(ptr: *mut CatSolver) -> c_int {
+    match (*ptr).sat.solve() {
+        Some(true) => 10,
+        Some(false) => 20,
+        None => 0,
+    }
+}
+
+/// Returns `lit` if it is true in the last solution, `-lit` if it is false
+/// and 0 if it is a don't-care.
+/// # Safety
+/// `ptr` must be a live solver from `cat_solver_init`, whose last solve
+/// returned 10.
+#[no_mangle]
+pub unsafe extern "C" fn cat_solver_value(ptr: *const CatSolver, lit: c_int) -> c_int {
+    match (*ptr).sat.value(lit) {
+        Some(true) => lit,
+        Some(false) => -lit,
+        None => 0,
+    }
+}
+
+/// Returns 1 if the assumption `lit` was used to prove unsatisfiability
+/// in the last solve, as `Solver::failed` does, and 0 otherwise.
+/// # Safety
+/// `ptr` must be a live solver from `cat_solver_init`, whose last solve
+/// returned 20.
+#[no_mangle]
+pub unsafe extern "C" fn cat_solver_failed(ptr: *const CatSolver, lit: c_int) -> c_int {
+    (*ptr).sat.failed(lit) as c_int
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capi() {
+        unsafe {
+            let ptr = cat_solver_init();
+            for lit in [1, 2, 0, -1, 0] {
+                cat_solver_add(ptr, lit);
+            }
+            assert_eq!(cat_solver_solve(ptr), 10);
+            assert_eq!(cat_solver_value(ptr, 1), -1);
+            assert_eq!(cat_solver_value(ptr, -2), 2);
+            cat_solver_assume(ptr, -2);
+            assert_eq!(cat_solver_solve(ptr), 20);
+            assert_eq!(cat_solver_failed(ptr, -2), 1);
+            assert_eq!(cat_solver_set_limit(ptr, c"conflicts".as_ptr(), 10), 0);
+            assert_eq!(cat_solver_set_limit(ptr, c"bad".as_ptr(), 10), -1);
+            cat_solver_release(ptr);
+        }
+    }
+}
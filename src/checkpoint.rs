@@ -0,0 +1,118 @@
+use crate::{Cnf, Error, Solver};
+use std::ffi::CString;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// The prefix of the comment lines holding the wrapper's state.
+const PREFIX: &str = "c cat_solver ";
+
+impl Solver {
+    /// Saves the state the wrapper can reconstruct to a file: the recorded
+    /// clauses in DIMACS format, preceded by comment lines holding the
+    /// reserved variables, configuration, options, pending limits and soft
+    /// clauses. Kissat has no serialization, so nothing of its search is
+    /// saved: learned clauses, phases and the progress of an interrupted
+    /// solve are lost, and a restored solver starts its search over. The
+    /// file is written next to `path` first and then renamed, so a crash
+    /// never leaves a partial checkpoint behind.
+    pub fn checkpoint<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let mut w = BufWriter::new(File::create(&tmp)?);
+        writeln!(w, "{}vars {}", PREFIX, self.max_var)?;
+        if let Some(config) = &self.config {
+            writeln!(w, "{}config {}", PREFIX, config.to_string_lossy())?;
+        }
+        for (name, value) in &self.options {
+            writeln!(w, "{}option {} {}", PREFIX, name.to_string_lossy(), value)?;
+        }
+        if let Some(conflicts) = self.limits.conflicts {
+            writeln!(w, "{}limit conflicts {}", PREFIX, conflicts)?;
+        }
+        if let Some(decisions) = self.limits.decisions {
+            writeln!(w, "{}limit decisions {}", PREFIX, decisions)?;
+        }
+        for (relax, weight) in &self.soft {
+            writeln!(w, "{}soft {} {}", PREFIX, relax, weight)?;
+        }
+        self.cnf.write_dimacs(&mut w)?;
+        w.into_inner().map_err(io::IntoInnerError::into_error)?.sync_all()?;
+        fs::rename(&tmp, path)
+    }
+
+    /// Creates a solver from a file written by `checkpoint`, equivalent to
+    /// the saved one before its first solve.
+    pub fn restore<P: AsRef<Path>>(path: P) -> Result<Solver, Error> {
+        let bytes = fs::read(path).map_err(|err| Error::new(&err.to_string()))?;
+        let cnf = Cnf::read_dimacs(&bytes[..])?;
+        let mut sat = Solver::new();
+        sat.max_var = cnf.num_vars();
+        let text = String::from_utf8_lossy(&bytes);
+        let mut options = Vec::new();
+        for line in text.lines().filter_map(|line| line.strip_prefix(PREFIX)) {
+            let error = || Error::new(&format!("invalid checkpoint line: {}", line));
+            let fields: Vec<&str> = line.split_ascii_whitespace().collect();
+            match fields[..] {
+                ["vars", vars] => sat.max_var = sat.max_var.max(vars.parse().map_err(|_| error())?),
+                ["config", name] => {
+                    let name = CString::new(name).map_err(|_| error())?;
+                    if !Solver::known_config(&name) {
+                        return Err(error());
+                    }
+                    sat.config = Some(name);
+                }
+                ["option", name, value] => options.push((name, value.parse().map_err(|_| error())?)),
+                ["limit", name, limit] => sat.set_limit(name, limit.parse().map_err(|_| error())?)?,
+                ["soft", relax, weight] => {
+                    let relax = relax.parse().map_err(|_| error())?;
+                    sat.soft.push((relax, weight.parse().map_err(|_| error())?));
+                }
+                _ => return Err(error()),
+            }
+        }
+        // recorded only, so that load applies the configuration and the
+        // options once, before the clauses
+        sat.options = Solver::check_options(&options)?;
+        sat.cnf = cnf;
+        sat.load();
+        Ok(sat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoint() {
+        let path = std::env::temp_dir().join(format!("cat_solver_checkpoint_{}.cnf", std::process::id()));
+        let mut sat = Solver::with_config(10, "sat").unwrap();
+        sat.add_clause([1, 2]);
+        sat.add_clause([-1]);
+        let relax = sat.add_soft_clause(&[-2], 5);
+        sat.set_target(1).unwrap();
+        sat.set_limit("conflicts", 1000).unwrap();
+        sat.checkpoint(&path).unwrap();
+        let mut restored = Solver::restore(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(restored.cnf(), sat.cnf());
+        assert_eq!(restored.num_vars(), 10);
+        assert_eq!(restored.config, sat.config);
+        assert_eq!(restored.options, sat.options);
+        assert_eq!(restored.limits, sat.limits);
+        assert_eq!(restored.soft_clauses(), [(relax, 5)]);
+        assert_eq!(restored.solve(), Some(true));
+        assert_eq!(restored.value(relax), Some(true));
+    }
+
+    #[test]
+    fn restore_errors() {
+        let path = std::env::temp_dir().join(format!("cat_solver_restore_{}.cnf", std::process::id()));
+        fs::write(&path, "c cat_solver frobnicate\n1 0\n").unwrap();
+        let result = Solver::restore(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(result.err(), Some(Error::new("invalid checkpoint line: frobnicate")));
+    }
+}
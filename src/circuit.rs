@@ -0,0 +1,94 @@
+use crate::Solver;
+
+/// A builder for combinational circuits over and-inverter graphs, in the
+/// style of AIGER, which Tseitin-encodes each node into a solver as it is
+/// created. Nodes are identified by literals: negating a node's literal
+/// negates the node, so inverters cost nothing.
+/// # Examples
+/// ```
+/// let mut sat = cat_solver::Solver::new();
+/// let mut circuit = cat_solver::Circuit::new(&mut sat);
+/// let a = circuit.input();
+/// let b = circuit.input();
+/// let nand = circuit.and(a, b);
+/// let nand = circuit.not(nand);
+/// circuit.output(nand);
+/// sat.add_clause([-nand]);
+/// assert_eq!(sat.solve(), Some(true));
+/// assert_eq!((sat.value(a), sat.value(b)), (Some(true), Some(true)));
+/// ```
+pub struct Circuit<'a> {
+    sat: &'a mut Solver,
+    inputs: Vec<i32>,
+    outputs: Vec<i32>,
+}
+
+impl<'a> Circuit<'a> {
+    /// Starts a circuit whose nodes are encoded into `sat`.
+    pub fn new(sat: &'a mut Solver) -> Self {
+        Circuit {
+            sat,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Adds an input, a fresh unconstrained variable, and returns it.
+    pub fn input(&mut self) -> i32 {
+        let lit = self.sat.new_var();
+        self.inputs.push(lit);
+        lit
+    }
+
+    /// Adds an and-gate over two nodes and returns its output.
+    pub fn and(&mut self, a: i32, b: i32) -> i32 {
+        self.sat.and_gate(&[a, b])
+    }
+
+    /// Returns the negation of a node. No clauses are added.
+    #[inline]
+    pub fn not(&self, a: i32) -> i32 {
+        debug_assert!(crate::valid_lit(a));
+        -a
+    }
+
+    /// Marks a node as an output of the circuit.
+    pub fn output(&mut self, lit: i32) {
+        self.outputs.push(lit);
+    }
+
+    /// Returns the inputs, in the order they were added.
+    pub fn inputs(&self) -> &[i32] {
+        &self.inputs
+    }
+
+    /// Returns the outputs, in the order they were marked.
+    pub fn outputs(&self) -> &[i32] {
+        &self.outputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xor() {
+        let mut sat = Solver::new();
+        let mut circuit = Circuit::new(&mut sat);
+        let a = circuit.input();
+        let b = circuit.input();
+        let both = circuit.and(a, b);
+        let neither = circuit.and(-a, -b);
+        let xor = circuit.and(circuit.not(both), circuit.not(neither));
+        circuit.output(xor);
+        assert_eq!(circuit.inputs(), [a, b]);
+        assert_eq!(circuit.outputs(), [xor]);
+        for (va, vb) in [(false, false), (false, true), (true, false), (true, true)] {
+            sat.assume(if va { a } else { -a });
+            sat.assume(if vb { b } else { -b });
+            assert_eq!(sat.solve(), Some(true));
+            assert_eq!(sat.value(xor), Some(va != vb));
+        }
+    }
+}
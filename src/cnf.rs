@@ -0,0 +1,252 @@
+use std::slice;
+use std::sync::Arc;
+
+/// A formula in conjunctive normal form, stored as DIMACS literals.
+/// Each clause is kept in one flat buffer, terminated by a zero.
+/// # Examples
+/// ```
+/// let mut cnf = cat_solver::Cnf::new();
+/// cnf.add_clause([1, -2]);
+/// cnf.add_clause([2]);
+/// assert_eq!(cnf.num_vars(), 2);
+/// assert_eq!(cnf.clauses().collect::<Vec<_>>(), [&[1, -2][..], &[2][..]]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cnf {
+    lits: Vec<i32>,
+    num_clauses: usize,
+    max_var: i32,
+}
+
+impl Cnf {
+    /// Constructs an empty formula.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds the given clause to the formula. All literals must be non-zero
+    /// and different from `i32::MIN`; they may also be given as `Lit`s.
+    #[inline]
+    pub fn add_clause<I>(&mut self, clause: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<i32>,
+    {
+        for lit in clause {
+            let lit: i32 = lit.into();
+            debug_assert!(crate::valid_lit(lit));
+            self.max_var = self.max_var.max(lit.abs());
+            self.lits.push(lit);
+        }
+        self.lits.push(0);
+        self.num_clauses += 1;
+    }
+
+    /// Reserves capacity for at least `additional` more literals, counting
+    /// the terminating zero of each clause as a literal.
+    pub fn reserve(&mut self, additional: usize) {
+        self.lits.reserve(additional);
+    }
+
+    /// Removes all clauses, keeping the allocated capacity.
+    pub fn clear(&mut self) {
+        self.lits.clear();
+        self.num_clauses = 0;
+        self.max_var = 0;
+    }
+
+    /// Raises `num_vars()` to at least `max_var`, even if no clause uses it.
+    pub(crate) fn reserve_vars(&mut self, max_var: i32) {
+        self.max_var = self.max_var.max(max_var);
+    }
+
+    /// Returns the largest variable occurring in the formula, or declared by
+    /// its DIMACS header when read with `reserve_from_header`.
+    #[inline]
+    pub fn num_vars(&self) -> i32 {
+        self.max_var
+    }
+
+    /// Returns the number of clauses.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.num_clauses
+    }
+
+    /// Returns whether the formula has no clauses.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.num_clauses == 0
+    }
+
+    /// Iterates over the clauses, in the order they were added.
+    pub fn clauses(&self) -> Clauses<'_> {
+        Clauses {
+            lits: self.lits.iter(),
+            start: &self.lits,
+        }
+    }
+
+    /// Returns a hash of the formula which does not depend on the order of
+    /// the clauses, nor on the order of the literals within each clause.
+    /// The clauses are sorted into a canonical form and hashed with 64-bit
+    /// FNV-1a, so the value is stable across platforms and releases. Like
+    /// any hash, different formulas may collide.
+    pub fn fingerprint(&self) -> u64 {
+        let mut clauses: Vec<Vec<i32>> = self
+            .clauses()
+            .map(|clause| {
+                let mut clause = clause.to_vec();
+                clause.sort_unstable();
+                clause
+            })
+            .collect();
+        clauses.sort_unstable();
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for lit in clauses.iter().flat_map(|clause| clause.iter().chain([&0])) {
+            for byte in lit.to_le_bytes() {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+        hash
+    }
+
+    /// Returns an immutable copy of the formula which is cheap to clone and
+    /// can be shared with other threads, for example to solve it while the
+    /// next formula is built. Taking the snapshot copies all literals once.
+    pub fn snapshot(&self) -> CnfSnapshot {
+        CnfSnapshot {
+            lits: self.lits.as_slice().into(),
+            num_clauses: self.num_clauses,
+            max_var: self.max_var,
+        }
+    }
+}
+
+/// An immutable formula shared by reference counting, created by
+/// `Cnf::snapshot`. Solving it with `Solver::from` copies the clauses again,
+/// into the solver's own record and into Kissat.
+/// # Examples
+/// ```
+/// let mut cnf = cat_solver::Cnf::new();
+/// cnf.add_clause([1, 2]);
+/// let snapshot = cnf.snapshot();
+/// let worker = std::thread::spawn(move || cat_solver::Solver::from(&snapshot).solve());
+/// cnf.add_clause([-1]);
+/// cnf.add_clause([-2]);
+/// assert_eq!(worker.join().unwrap(), Some(true));
+/// assert_eq!(cat_solver::is_satisfiable(&cnf), Some(false));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CnfSnapshot {
+    lits: Arc<[i32]>,
+    num_clauses: usize,
+    max_var: i32,
+}
+
+impl CnfSnapshot {
+    /// Returns the largest variable occurring in the formula.
+    #[inline]
+    pub fn num_vars(&self) -> i32 {
+        self.max_var
+    }
+
+    /// Returns the number of clauses.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.num_clauses
+    }
+
+    /// Returns whether the formula has no clauses.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.num_clauses == 0
+    }
+
+    /// Iterates over the clauses, in the order they were added.
+    pub fn clauses(&self) -> Clauses<'_> {
+        Clauses {
+            lits: self.lits.iter(),
+            start: &self.lits,
+        }
+    }
+
+    /// Copies the snapshot back into a formula which can be extended.
+    pub fn to_cnf(&self) -> Cnf {
+        Cnf {
+            lits: self.lits.to_vec(),
+            num_clauses: self.num_clauses,
+            max_var: self.max_var,
+        }
+    }
+}
+
+/// Iterator over the clauses of a `Cnf`, created by `Cnf::clauses`.
+#[derive(Clone, Debug)]
+pub struct Clauses<'a> {
+    lits: slice::Iter<'a, i32>,
+    start: &'a [i32],
+}
+
+impl<'a> Iterator for Clauses<'a> {
+    type Item = &'a [i32];
+
+    fn next(&mut self) -> Option<&'a [i32]> {
+        let rest = self.start;
+        let len = self.lits.position(|&lit| lit == 0)?;
+        self.start = &rest[len + 1..];
+        Some(&rest[..len])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clauses() {
+        let mut cnf = Cnf::new();
+        assert!(cnf.is_empty());
+        cnf.add_clause([1, -3]);
+        cnf.add_clause(Vec::<i32>::new());
+        cnf.add_clause([2]);
+        assert_eq!(cnf.len(), 3);
+        assert_eq!(cnf.num_vars(), 3);
+        let clauses: Vec<&[i32]> = cnf.clauses().collect();
+        assert_eq!(clauses, [&[1, -3][..], &[][..], &[2][..]]);
+        cnf.clear();
+        assert_eq!(cnf, Cnf::new());
+    }
+
+    #[test]
+    fn fingerprint() {
+        let mut a = Cnf::new();
+        a.add_clause([1, -3]);
+        a.add_clause([2]);
+        let mut b = Cnf::new();
+        b.add_clause([2]);
+        b.add_clause([-3, 1]);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        b.add_clause(Vec::<i32>::new());
+        assert_ne!(a.fingerprint(), b.fingerprint());
+        let mut c = Cnf::new();
+        c.add_clause([1]);
+        c.add_clause([-3, 2]);
+        assert_ne!(a.fingerprint(), c.fingerprint());
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut cnf = Cnf::new();
+        cnf.add_clause([1, -3]);
+        let snapshot = cnf.snapshot();
+        cnf.add_clause([2]);
+        let clone = snapshot.clone();
+        assert_eq!(clone.len(), 1);
+        assert_eq!(clone.num_vars(), 3);
+        assert_eq!(clone.clauses().collect::<Vec<_>>(), [&[1, -3][..]]);
+        assert_eq!(clone.to_cnf().snapshot(), snapshot);
+        assert_eq!(cnf.snapshot().to_cnf(), cnf);
+    }
+}
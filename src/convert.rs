@@ -0,0 +1,40 @@
+use crate::Cnf;
+use varisat::ExtendFormula;
+
+/// Converts the formula for the `varisat` solver, for cross-checking.
+impl From<&Cnf> for varisat::CnfFormula {
+    fn from(cnf: &Cnf) -> Self {
+        let mut formula = varisat::CnfFormula::new();
+        formula.set_var_count(cnf.num_vars() as usize);
+        let mut lits = Vec::new();
+        for clause in cnf.clauses() {
+            lits.clear();
+            lits.extend(clause.iter().map(|&lit| varisat::Lit::from_dimacs(lit as isize)));
+            formula.add_clause(&lits);
+        }
+        formula
+    }
+}
+
+impl From<Cnf> for varisat::CnfFormula {
+    fn from(cnf: Cnf) -> Self {
+        Self::from(&cnf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn varisat() {
+        let mut cnf = Cnf::new();
+        cnf.add_clause([1, -3]);
+        cnf.add_clause([2]);
+        let formula = varisat::CnfFormula::from(&cnf);
+        assert_eq!(formula.var_count(), 3);
+        assert_eq!(formula.len(), 2);
+        let clauses: Vec<Vec<isize>> = formula.iter().map(|clause| clause.iter().map(|lit| lit.to_dimacs()).collect()).collect();
+        assert_eq!(clauses, [vec![1, -3], vec![2]]);
+    }
+}
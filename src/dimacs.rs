@@ -0,0 +1,554 @@
+use crate::{Cnf, Error, Model, Solver};
+use std::io::{self, BufRead, Write};
+
+/// Maximal width of the `v` lines written for models.
+const LINE_WIDTH: usize = 78;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum State {
+    #[default]
+    LineStart,
+    Comment,
+    Header,
+    Body,
+    /// After a `%` line, which ends the formula in some benchmark files.
+    End,
+}
+
+/// Options for `Cnf::read_dimacs_with`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DimacsOptions {
+    /// Take the variable count of the header as the formula's `num_vars()`
+    /// up front, so that a solver built from it reserves all variables at
+    /// once, even those which no clause uses.
+    pub reserve_from_header: bool,
+    /// Reject variables beyond the header's count, instead of extending
+    /// the formula to them. `Cnf::read_dimacs_info` reports them as a
+    /// warning otherwise.
+    pub strict: bool,
+}
+
+/// A DIMACS CNF parser which accepts its input in arbitrary chunks.
+#[derive(Debug, Default)]
+pub(crate) struct Parser {
+    cnf: Cnf,
+    options: DimacsOptions,
+    /// Only count the clauses, instead of storing them in `cnf`.
+    discard: bool,
+    max_var: i32,
+    num_clauses: usize,
+    clause: Vec<i32>,
+    header: Option<(i32, usize)>,
+    header_line: Vec<u8>,
+    state: State,
+    negative: bool,
+    digits: bool,
+    value: i64,
+    line: usize,
+}
+
+impl Parser {
+    pub(crate) fn feed(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        for &b in bytes {
+            match self.state {
+                State::End => {}
+                State::Comment => {
+                    if b == b'\n' {
+                        self.newline();
+                    }
+                }
+                State::Header => {
+                    if b == b'\n' {
+                        self.parse_header()?;
+                        self.newline();
+                    } else {
+                        self.header_line.push(b);
+                    }
+                }
+                State::LineStart if b == b'c' => self.state = State::Comment,
+                State::LineStart if b == b'p' => self.state = State::Header,
+                State::LineStart if b == b'%' => self.state = State::End,
+                State::LineStart | State::Body => self.body(b)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the parsed formula, after checking the input is complete.
+    pub(crate) fn finish(mut self) -> Result<Cnf, Error> {
+        self.end()?;
+        Ok(self.cnf)
+    }
+
+    /// Returns the statistics of the input parsed so far.
+    fn info(&self) -> DimacsInfo {
+        DimacsInfo {
+            declared: self.header,
+            num_vars: self.max_var,
+            num_clauses: self.num_clauses,
+        }
+    }
+
+    fn end(&mut self) -> Result<(), Error> {
+        match self.state {
+            State::Header => self.parse_header()?,
+            State::LineStart | State::Body => self.end_token()?,
+            State::Comment | State::End => {}
+        }
+        if !self.clause.is_empty() {
+            return Err(self.error("unterminated clause"));
+        }
+        Ok(())
+    }
+
+    fn body(&mut self, b: u8) -> Result<(), Error> {
+        match b {
+            b'0'..=b'9' => {
+                self.value = 10 * self.value + i64::from(b - b'0');
+                if self.value > i64::from(i32::MAX) {
+                    return Err(self.error("literal out of range"));
+                }
+                self.digits = true;
+            }
+            b'-' if !self.negative && !self.digits => self.negative = true,
+            b' ' | b'\t' | b'\r' | b'\n' => {
+                self.end_token()?;
+                if b == b'\n' {
+                    self.newline();
+                    return Ok(());
+                }
+            }
+            _ => return Err(self.error("unexpected character")),
+        }
+        self.state = State::Body;
+        Ok(())
+    }
+
+    fn end_token(&mut self) -> Result<(), Error> {
+        if !self.digits {
+            if self.negative {
+                return Err(self.error("expected digit"));
+            }
+            return Ok(());
+        }
+        let value = if self.negative { -self.value } else { self.value };
+        let lit = value as i32;
+        if lit == 0 {
+            self.num_clauses += 1;
+            if self.discard {
+                self.clause.clear();
+            } else {
+                self.cnf.add_clause(self.clause.drain(..));
+            }
+        } else {
+            if self.options.strict && self.header.is_some_and(|(vars, _)| lit.abs() > vars) {
+                return Err(self.error("variable exceeds header"));
+            }
+            self.max_var = self.max_var.max(lit.abs());
+            self.clause.push(lit);
+        }
+        self.negative = false;
+        self.digits = false;
+        self.value = 0;
+        Ok(())
+    }
+
+    fn parse_header(&mut self) -> Result<(), Error> {
+        if self.header.is_some() {
+            return Err(self.error("duplicate header"));
+        }
+        let line = String::from_utf8_lossy(&self.header_line).into_owned();
+        let mut fields = line.split_ascii_whitespace();
+        let header = match (fields.next(), fields.next(), fields.next(), fields.next()) {
+            (Some("cnf"), Some(vars), Some(clauses), None) => vars.parse().ok().zip(clauses.parse().ok()),
+            _ => None,
+        };
+        match header {
+            Some((vars, clauses)) if vars >= 0 => {
+                self.header = Some((vars, clauses));
+                if self.options.reserve_from_header {
+                    self.cnf.reserve_vars(vars);
+                }
+            }
+            _ => return Err(self.error("invalid header")),
+        }
+        Ok(())
+    }
+
+    fn newline(&mut self) {
+        self.line += 1;
+        self.state = State::LineStart;
+    }
+
+    fn error(&self, msg: &str) -> Error {
+        Error::new(&format!("line {}: {}", self.line + 1, msg))
+    }
+}
+
+fn io_error(err: io::Error) -> Error {
+    Error::new(&err.to_string())
+}
+
+/// Feeds the whole input of `reader` to `parser`.
+fn feed_reader<R: BufRead>(parser: &mut Parser, mut reader: R) -> Result<(), Error> {
+    loop {
+        let buf = match reader.fill_buf() {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(io_error(err)),
+        };
+        if buf.is_empty() {
+            return Ok(());
+        }
+        let len = buf.len();
+        parser.feed(buf)?;
+        reader.consume(len);
+    }
+}
+
+/// A sink which parses the DIMACS CNF bytes written to it into a `Cnf`,
+/// so that any source can be copied into it with `io::copy`. Tokens and
+/// clauses may be split across writes arbitrarily.
+/// # Examples
+/// ```
+/// use std::io::Write;
+/// let mut builder = cat_solver::DimacsBuilder::new();
+/// builder.write_all(b"p cnf 2 2\n1 -").unwrap();
+/// builder.write_all(b"2 0\n2 0\n").unwrap();
+/// let cnf = builder.into_cnf().unwrap();
+/// assert_eq!(cnf.clauses().collect::<Vec<_>>(), [&[1, -2][..], &[2][..]]);
+/// ```
+#[derive(Debug, Default)]
+pub struct DimacsBuilder {
+    parser: Parser,
+    /// The first parse error, returned by all later calls.
+    error: Option<Error>,
+}
+
+impl DimacsBuilder {
+    /// Constructs an empty builder.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Constructs an empty builder which reads the header as instructed.
+    pub fn with_options(options: &DimacsOptions) -> Self {
+        DimacsBuilder {
+            parser: Parser {
+                options: *options,
+                ..Default::default()
+            },
+            error: None,
+        }
+    }
+
+    /// Returns the formula, after checking the input is complete. Returns
+    /// the parse error instead if a write failed.
+    pub fn into_cnf(self) -> Result<Cnf, Error> {
+        match self.error {
+            Some(err) => Err(err),
+            None => self.parser.finish(),
+        }
+    }
+}
+
+/// Parse errors are reported as `io::ErrorKind::InvalidData`.
+impl Write for DimacsBuilder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.error.is_none() {
+            self.error = self.parser.feed(buf).err();
+        }
+        match &self.error {
+            Some(err) => Err(io::Error::new(io::ErrorKind::InvalidData, err.msg.clone())),
+            None => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Statistics of a DIMACS CNF input, as returned by `validate_dimacs` and
+/// `Cnf::read_dimacs_info`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DimacsInfo {
+    /// The numbers of variables and clauses declared in the header, if any.
+    pub declared: Option<(i32, usize)>,
+    /// The largest variable occurring in a clause.
+    pub num_vars: i32,
+    /// The number of clauses.
+    pub num_clauses: usize,
+}
+
+impl DimacsInfo {
+    /// Returns a warning if the header is missing or disagrees with the
+    /// clauses: if the clause count differs, or a clause uses a variable
+    /// beyond the declared count. Declaring unused variables is allowed.
+    pub fn warning(&self) -> Option<String> {
+        let Some((vars, clauses)) = self.declared else {
+            return Some("missing header".to_string());
+        };
+        if clauses != self.num_clauses {
+            Some(format!("header declares {} clauses, found {}", clauses, self.num_clauses))
+        } else if vars < self.num_vars {
+            Some(format!("header declares {} variables, found variable {}", vars, self.num_vars))
+        } else {
+            None
+        }
+    }
+}
+
+/// Parses a DIMACS CNF input, as `Cnf::read_dimacs` does, and returns its
+/// statistics. The clauses are only counted, not stored, so that large
+/// files can be checked cheaply before they are solved.
+pub fn validate_dimacs<R: BufRead>(reader: R) -> Result<DimacsInfo, Error> {
+    let mut parser = Parser {
+        discard: true,
+        ..Default::default()
+    };
+    feed_reader(&mut parser, reader)?;
+    parser.end()?;
+    Ok(parser.info())
+}
+
+/// Writes the given literals as DIMACS `v` lines, terminated by `v 0`.
+pub(crate) fn write_values<W, I>(w: &mut W, lits: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = i32>,
+{
+    let mut line = String::from("v");
+    for lit in lits.into_iter().chain([0]) {
+        let token = format!(" {}", lit);
+        if line.len() + token.len() > LINE_WIDTH {
+            writeln!(w, "{}", line)?;
+            line.truncate(1);
+        }
+        line.push_str(&token);
+    }
+    writeln!(w, "{}", line)
+}
+
+impl Cnf {
+    /// Reads a formula in DIMACS CNF format. The header line is optional;
+    /// comment lines and a final `%` line, as found in some benchmark
+    /// files, are skipped.
+    pub fn read_dimacs<R: BufRead>(reader: R) -> Result<Cnf, Error> {
+        Cnf::read_dimacs_with(reader, &DimacsOptions::default())
+    }
+
+    /// Reads a formula in DIMACS CNF format like `read_dimacs`, with the
+    /// given options for how the header is used.
+    pub fn read_dimacs_with<R: BufRead>(reader: R, options: &DimacsOptions) -> Result<Cnf, Error> {
+        let mut parser = Parser {
+            options: *options,
+            ..Default::default()
+        };
+        feed_reader(&mut parser, reader)?;
+        parser.finish()
+    }
+
+    /// Reads a formula like `read_dimacs_with`, along with the statistics
+    /// `validate_dimacs` reports, so that a header which the clauses exceed
+    /// can still be warned about through `DimacsInfo::warning` when the
+    /// options are not `strict`.
+    pub fn read_dimacs_info<R: BufRead>(reader: R, options: &DimacsOptions) -> Result<(Cnf, DimacsInfo), Error> {
+        let mut parser = Parser {
+            options: *options,
+            ..Default::default()
+        };
+        feed_reader(&mut parser, reader)?;
+        parser.end()?;
+        let info = parser.info();
+        Ok((parser.cnf, info))
+    }
+
+    /// Parses a formula in DIMACS CNF format from bytes already in memory,
+    /// like `read_dimacs_with`, in a single pass without copying them.
+    pub fn parse_dimacs(bytes: &[u8], options: &DimacsOptions) -> Result<Cnf, Error> {
+        let mut parser = Parser {
+            options: *options,
+            ..Default::default()
+        };
+        parser.feed(bytes)?;
+        parser.finish()
+    }
+
+    /// Writes the formula in DIMACS CNF format. Many small writes are made,
+    /// so the writer should be buffered.
+    pub fn write_dimacs<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(w, "p cnf {} {}", self.num_vars(), self.len())?;
+        for clause in self.clauses() {
+            for lit in clause {
+                write!(w, "{} ", lit)?;
+            }
+            writeln!(w, "0")?;
+        }
+        Ok(())
+    }
+}
+
+impl Model {
+    /// Writes the assigned literals of the model as DIMACS `v` lines,
+    /// as in the SAT competition output format.
+    pub fn write_dimacs<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let lits = (1..=self.num_vars()).filter_map(|var| self.value(var).map(|val| if val { var } else { -var }));
+        write_values(w, lits)
+    }
+}
+
+impl Solver {
+    /// Writes the assigned literals of the last solution as DIMACS `v`
+    /// lines, like `Model::write_dimacs`, but reads the values straight from
+    /// the solver instead of building a `Model` first. It is an error to
+    /// call this unless the last solve returned `Some(true)`.
+    pub fn write_model<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        if self.result != Some(Ok(true)) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "no model available"));
+        }
+        let lits = (1..=self.num_vars()).filter_map(|var| self.value(var).map(|val| if val { var } else { -var }));
+        write_values(w, lits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read() {
+        let text = "c example\np cnf 3 2\n1 -3 0\n2\n 3 0\n%\n0\n";
+        let cnf = Cnf::read_dimacs(text.as_bytes()).unwrap();
+        assert_eq!(cnf.num_vars(), 3);
+        assert_eq!(cnf.clauses().collect::<Vec<_>>(), [&[1, -3][..], &[2, 3][..]]);
+    }
+
+    #[test]
+    fn read_errors() {
+        assert_eq!(Cnf::read_dimacs(&b"1 2"[..]), Err(Error::new("line 1: unterminated clause")));
+        assert_eq!(Cnf::read_dimacs(&b"1 x 0"[..]), Err(Error::new("line 1: unexpected character")));
+        assert_eq!(Cnf::read_dimacs(&b"1 - 0"[..]), Err(Error::new("line 1: expected digit")));
+        assert_eq!(Cnf::read_dimacs(&b"p cnf 1\n"[..]), Err(Error::new("line 1: invalid header")));
+        assert_eq!(Cnf::read_dimacs(&b"c\n3000000000 0"[..]), Err(Error::new("line 2: literal out of range")));
+    }
+
+    #[test]
+    fn read_options() {
+        let text = b"p cnf 4 1\n1 -3 0\n";
+        let reserve = DimacsOptions {
+            reserve_from_header: true,
+            ..Default::default()
+        };
+        assert_eq!(Cnf::read_dimacs(&text[..]).unwrap().num_vars(), 3);
+        assert_eq!(Cnf::read_dimacs_with(&text[..], &reserve).unwrap().num_vars(), 4);
+        let text = b"p cnf 2 1\n1 -3 0\n";
+        let strict = DimacsOptions {
+            strict: true,
+            ..Default::default()
+        };
+        assert_eq!(Cnf::read_dimacs_with(&text[..], &strict), Err(Error::new("line 2: variable exceeds header")));
+        assert_eq!(Cnf::read_dimacs_with(&text[..], &reserve).unwrap().num_vars(), 3);
+        let (cnf, info) = Cnf::read_dimacs_info(&text[..], &reserve).unwrap();
+        assert_eq!(cnf.num_vars(), 3);
+        assert_eq!(info.warning().unwrap(), "header declares 2 variables, found variable 3");
+        let (_, info) = Cnf::read_dimacs_info(&b"p cnf 4 1\n1 -3 0\n"[..], &reserve).unwrap();
+        assert_eq!((info.num_vars, info.warning()), (3, None));
+        assert!(Cnf::read_dimacs_info(&text[..], &strict).is_err());
+    }
+
+    #[test]
+    fn parse() {
+        let text = b"p cnf 4 2\n1 -3 0\n2 0\n";
+        let reserve = DimacsOptions {
+            reserve_from_header: true,
+            ..Default::default()
+        };
+        assert_eq!(Cnf::parse_dimacs(text, &reserve), Cnf::read_dimacs_with(&text[..], &reserve));
+        assert_eq!(Cnf::parse_dimacs(b"1 2", &reserve), Err(Error::new("line 1: unterminated clause")));
+    }
+
+    #[test]
+    fn validate() {
+        let info = validate_dimacs(&b"c x\np cnf 3 2\n1 -3 0\n2 0\n"[..]).unwrap();
+        assert_eq!(info, DimacsInfo { declared: Some((3, 2)), num_vars: 3, num_clauses: 2 });
+        assert_eq!(info.warning(), None);
+        let info = validate_dimacs(&b"p cnf 2 3\n1 -3 0\n"[..]).unwrap();
+        assert_eq!(info.warning().unwrap(), "header declares 3 clauses, found 1");
+        let info = validate_dimacs(&b"p cnf 2 1\n1 -3 0\n"[..]).unwrap();
+        assert_eq!(info.warning().unwrap(), "header declares 2 variables, found variable 3");
+        assert_eq!(validate_dimacs(&b"1 0"[..]).unwrap().warning().unwrap(), "missing header");
+        assert_eq!(validate_dimacs(&b"1 x 0"[..]), Err(Error::new("line 1: unexpected character")));
+    }
+
+    #[test]
+    fn chunks() {
+        let text = b"p cnf 2 2\n-1 2 0\n-2 0\n";
+        let mut parser = Parser::default();
+        for chunk in text.chunks(3) {
+            parser.feed(chunk).unwrap();
+        }
+        assert_eq!(parser.finish(), Cnf::read_dimacs(&text[..]));
+    }
+
+    #[test]
+    fn builder() {
+        let text = b"c split\np cnf 3 2\n-1 23 0\n-2 0\n";
+        for size in 1..text.len() {
+            let mut builder = DimacsBuilder::new();
+            for chunk in text.chunks(size) {
+                builder.write_all(chunk).unwrap();
+            }
+            assert_eq!(builder.into_cnf(), Cnf::read_dimacs(&text[..]));
+        }
+        let mut builder = DimacsBuilder::new();
+        io::copy(&mut &text[..], &mut builder).unwrap();
+        assert_eq!(builder.into_cnf().unwrap().len(), 2);
+        let mut builder = DimacsBuilder::new();
+        assert_eq!(builder.write(b"1 x").unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert!(builder.write(b" 0\n").is_err());
+        assert_eq!(builder.into_cnf(), Err(Error::new("line 1: unexpected character")));
+        let mut builder = DimacsBuilder::new();
+        builder.write_all(b"1 2").unwrap();
+        assert_eq!(builder.into_cnf(), Err(Error::new("line 1: unterminated clause")));
+    }
+
+    #[test]
+    fn write() {
+        let mut cnf = Cnf::new();
+        cnf.add_clause([1, -2]);
+        cnf.add_clause([2]);
+        let mut out = Vec::new();
+        cnf.write_dimacs(&mut out).unwrap();
+        assert_eq!(out, b"p cnf 2 2\n1 -2 0\n2 0\n");
+        assert_eq!(Cnf::read_dimacs(&out[..]), Ok(cnf));
+    }
+
+    #[test]
+    fn write_model() {
+        let model = Model::from(vec![Some(true), None, Some(false)]);
+        let mut out = Vec::new();
+        model.write_dimacs(&mut out).unwrap();
+        assert_eq!(out, b"v 1 -3 0\n");
+        let model = Model::from(vec![Some(false); 30]);
+        let mut out = Vec::new();
+        model.write_dimacs(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.lines().all(|line| line.starts_with("v ") && line.len() <= LINE_WIDTH));
+        assert!(text.ends_with(" -30 0\n"));
+    }
+
+    #[test]
+    fn solver_write_model() {
+        let mut sat = Solver::new();
+        sat.add_clause([1]);
+        sat.add_clause([-2]);
+        let mut out = Vec::new();
+        assert!(sat.write_model(&mut out).is_err());
+        assert_eq!(sat.solve(), Some(true));
+        sat.write_model(&mut out).unwrap();
+        assert_eq!(out, b"v 1 -2 0\n");
+        sat.reset();
+        assert!(sat.write_model(&mut out).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{BitVec, Solver};
+
+/// Tseitin encodings of logic gates. Each helper introduces a fresh output
+/// variable and adds the clauses defining it in both directions, so the
+/// output is equivalent to the gate's function of its inputs. The word-level
+/// helpers constrain outputs given by the caller instead.
+impl Solver {
+    /// Returns a fresh literal equivalent to the conjunction of `inputs`.
+    /// It is true if `inputs` is empty. Adds `inputs.len() + 1` clauses.
+    pub fn and_gate(&mut self, inputs: &[i32]) -> i32 {
+        self.reserve_lits(inputs);
+        let out = self.new_var();
+        self.define_and(out, inputs);
+        out
+    }
+
+    /// Returns a fresh literal equivalent to the disjunction of `inputs`.
+    /// It is false if `inputs` is empty. Adds `inputs.len() + 1` clauses.
+    pub fn or_gate(&mut self, inputs: &[i32]) -> i32 {
+        -self.and_gate(&inputs.iter().map(|&lit| -lit).collect::<Vec<_>>())
+    }
+
+    /// Returns a fresh literal equivalent to `a` XOR `b`. Adds 4 clauses.
+    pub fn xor_gate(&mut self, a: i32, b: i32) -> i32 {
+        self.reserve_lits(&[a, b]);
+        let out = self.new_var();
+        self.add_clause([-out, a, b]);
+        self.add_clause([-out, -a, -b]);
+        self.add_clause([out, -a, b]);
+        self.add_clause([out, a, -b]);
+        out
+    }
+
+    /// Defines `out` as the conjunction of `inputs`, adding the clauses of
+    /// both directions: `out` implies each input, and all inputs together
+    /// imply `out`. Makes `out` true if `inputs` is empty. Adds
+    /// `inputs.len() + 1` clauses.
+    pub fn define_and(&mut self, out: i32, inputs: &[i32]) {
+        for &lit in inputs {
+            self.add_clause([-out, lit]);
+        }
+        self.add_clause(inputs.iter().map(|&lit| -lit).chain([out]));
+    }
+
+    /// Defines `out` as the disjunction of `inputs`, adding the clauses of
+    /// both directions, as `define_and` does for the negations. Makes `out`
+    /// false if `inputs` is empty. Adds `inputs.len() + 1` clauses.
+    pub fn define_or(&mut self, out: i32, inputs: &[i32]) {
+        self.define_and(-out, &inputs.iter().map(|&lit| -lit).collect::<Vec<_>>());
+    }
+
+    /// Constrains `a` to be lexicographically at most `b`, with the first
+    /// literals most significant and true above false: the lex-leader
+    /// constraint of static symmetry breaking, where `b` is the image of
+    /// `a` under a symmetry of the formula. It is encoded as the negation
+    /// of `BitVec::encode_ult(b, a)`, with the slices reversed to put the
+    /// least significant bit first. Panics unless `a` and `b` have the
+    /// same length.
+    pub fn add_lex_leader(&mut self, a: &[i32], b: &[i32]) {
+        self.reserve_lits(a);
+        self.reserve_lits(b);
+        let a = BitVec::from_lits(a.iter().rev().copied().collect());
+        let b = BitVec::from_lits(b.iter().rev().copied().collect());
+        let greater = b.encode_ult(self, &a);
+        self.add_clause([-greater]);
+    }
+
+    /// Constrains each `out[i]` to equal `a[i]` if `sel` is true and `b[i]`
+    /// otherwise, selecting between two bit-encoded integers. Adds 4 clauses
+    /// per bit. Panics unless the three slices have the same length.
+    pub fn encode_mux(&mut self, sel: i32, a: &[i32], b: &[i32], out: &[i32]) {
+        assert!(a.len() == out.len() && b.len() == out.len(), "mux operands differ in width");
+        for ((&a, &b), &out) in a.iter().zip(b).zip(out) {
+            self.add_clause([-sel, -a, out]);
+            self.add_clause([-sel, a, -out]);
+            self.add_clause([sel, -b, out]);
+            self.add_clause([sel, b, -out]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the gate output against `f` for every assignment of `n` inputs.
+    fn check<G, F>(n: i32, gate: G, f: F)
+    where
+        G: Fn(&mut Solver, &[i32]) -> i32,
+        F: Fn(&[bool]) -> bool,
+    {
+        let mut sat = Solver::new();
+        let inputs: Vec<i32> = (1..=n).collect();
+        let out = gate(&mut sat, &inputs);
+        for bits in 0..1 << n {
+            let values: Vec<bool> = (0..n).map(|i| bits >> i & 1 == 1).collect();
+            for (&var, &val) in inputs.iter().zip(&values) {
+                sat.assume(if val { var } else { -var });
+            }
+            assert_eq!(sat.solve(), Some(true));
+            assert_eq!(sat.value(out), Some(f(&values)));
+        }
+    }
+
+    #[test]
+    fn gates() {
+        check(3, |sat, inputs| sat.and_gate(inputs), |v| v.iter().all(|&b| b));
+        check(3, |sat, inputs| sat.or_gate(inputs), |v| v.iter().any(|&b| b));
+        check(2, |sat, inputs| sat.xor_gate(inputs[0], inputs[1]), |v| v[0] != v[1]);
+        check(0, |sat, inputs| sat.and_gate(inputs), |_| true);
+        check(0, |sat, inputs| sat.or_gate(inputs), |_| false);
+    }
+
+    #[test]
+    fn fresh_outputs() {
+        let mut sat = Solver::new();
+        assert_eq!(sat.and_gate(&[1, -2]), 3);
+        assert_eq!(sat.or_gate(&[-5]), -6);
+        assert_eq!(sat.xor_gate(4, 1), 7);
+        assert_eq!(sat.num_vars(), 7);
+    }
+
+    #[test]
+    fn definitions() {
+        let define = |and: bool| {
+            move |sat: &mut Solver, inputs: &[i32]| {
+                let out = inputs.len() as i32 + 1;
+                if and {
+                    sat.define_and(out, inputs);
+                } else {
+                    sat.define_or(out, inputs);
+                }
+                out
+            }
+        };
+        check(3, define(true), |v| v.iter().all(|&b| b));
+        check(3, define(false), |v| v.iter().any(|&b| b));
+        check(0, define(true), |_| true);
+        check(0, define(false), |_| false);
+    }
+
+    #[test]
+    fn lex_leader() {
+        // swapping 1 with 3 and 2 with 4 is a symmetry
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.add_clause([3, 4]);
+        assert_eq!(sat.count_models(100), 9);
+        sat.add_lex_leader(&[1, 2], &[3, 4]);
+        assert_eq!(sat.count_models(100), 6);
+        for model in sat.iter_solutions() {
+            let value = |lit: i32| model.value(lit).unwrap() as u8;
+            assert!((value(1), value(2)) <= (value(3), value(4)));
+        }
+        let mut sat = Solver::new();
+        sat.add_lex_leader(&[1, 2], &[3, 4]);
+        assert_eq!(sat.count_models(100), 10);
+    }
+
+    #[test]
+    fn mux() {
+        let (sel, a, b, out) = (1, [2, 3], [4, 5], [6, 7]);
+        let mut sat = Solver::new();
+        sat.encode_mux(sel, &a, &b, &out);
+        for bits in 0..1 << 5 {
+            let var = |i: i32| if bits >> i & 1 == 1 { i + 1 } else { -(i + 1) };
+            for i in 0..5 {
+                sat.assume(var(i));
+            }
+            assert_eq!(sat.solve(), Some(true));
+            let expected = if bits & 1 == 1 { a } else { b };
+            for (&lit, &bit) in out.iter().zip(&expected) {
+                assert_eq!(sat.value(lit), sat.value(bit));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn mux_width() {
+        Solver::new().encode_mux(1, &[2], &[3, 4], &[5]);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::Solver;
+use std::future::Future;
+use std::panic;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::task::JoinHandle;
+
+impl Solver {
+    /// Solves the formula on tokio's blocking thread pool, so the runtime is
+    /// not blocked, and hands the solver back together with the result.
+    /// Dropping the returned future terminates the solve through a terminate
+    /// callback, which is removed again once the solve returns.
+    /// Must be called from within a tokio runtime.
+    pub fn solve_async(mut self) -> SolveFuture {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let handle = tokio::task::spawn_blocking(move || {
+            let terminate = Box::new(move || flag.load(Ordering::Relaxed));
+            let result = self.solve_scoped(terminate).ok();
+            (self, result)
+        });
+        SolveFuture { handle, cancelled }
+    }
+}
+
+/// Future returned by `Solver::solve_async`.
+pub struct SolveFuture {
+    handle: JoinHandle<(Solver, Option<bool>)>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Future for SolveFuture {
+    type Output = (Solver, Option<bool>);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.handle).poll(cx) {
+            Poll::Ready(Ok(output)) => Poll::Ready(output),
+            Poll::Ready(Err(err)) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+            Poll::Ready(Err(err)) => panic!("solver task failed: {}", err),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for SolveFuture {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+
+    #[test]
+    fn solve_async() {
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.add_clause([-1]);
+        let (sat, result) = rt.block_on(sat.solve_async());
+        assert_eq!(result, Some(true));
+        assert_eq!(sat.value(2), Some(true));
+    }
+}
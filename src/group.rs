@@ -0,0 +1,88 @@
+use crate::Solver;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Stops a group of solvers at once, for example once one solver of a
+/// portfolio has decided. Members poll a shared flag through their terminate
+/// callbacks; `terminate_all` sets it, and can be called from any thread.
+#[derive(Clone, Debug, Default)]
+pub struct TerminationGroup {
+    flag: Arc<AtomicBool>,
+}
+
+/// A member's view of a `TerminationGroup`, created by `handle`.
+#[derive(Clone, Debug)]
+pub struct TerminateHandle {
+    flag: Arc<AtomicBool>,
+}
+
+impl TerminationGroup {
+    /// Creates a group which has not been terminated.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns a handle which reports when the group is terminated.
+    pub fn handle(&self) -> TerminateHandle {
+        TerminateHandle {
+            flag: self.flag.clone(),
+        }
+    }
+
+    /// Terminates the running solves of all members, and any they start
+    /// afterwards.
+    pub fn terminate_all(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether `terminate_all` has been called.
+    pub fn is_terminated(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+}
+
+impl TerminateHandle {
+    /// Returns whether the group has been terminated.
+    pub fn is_terminated(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+}
+
+impl Solver {
+    /// Makes the solver a member of `group`, by setting its terminate
+    /// callback to poll a handle of the group. This replaces any callback
+    /// set with `set_terminate`.
+    pub fn join_group(&mut self, group: &TerminationGroup) {
+        let handle = group.handle();
+        self.set_terminate(move || handle.is_terminated());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn terminate_all() {
+        let group = TerminationGroup::new();
+        let mut hard = crate::tests::pigeon_hole(10);
+        hard.join_group(&group);
+        let mut easy = Solver::new();
+        easy.join_group(&group);
+        easy.add_clause([1]);
+        let winner = || {
+            let result = easy.solve();
+            group.terminate_all();
+            result
+        };
+        let (hard, easy) = thread::scope(|s| {
+            let hard = s.spawn(|| hard.solve());
+            let easy = s.spawn(winner);
+            (hard.join().unwrap(), easy.join().unwrap())
+        });
+        assert_eq!(easy, Some(true));
+        assert_eq!(hard, None);
+        assert!(group.handle().is_terminated());
+    }
+}
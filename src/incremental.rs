@@ -0,0 +1,75 @@
+use crate::Solver;
+
+/// A minimal IPASIR-style interface to incremental SAT solvers, so that
+/// code can be written independently of the solver backend. Literals are
+/// non-zero DIMACS integers.
+pub trait IncrementalSat {
+    /// Adds a clause to the formula.
+    fn add_clause(&mut self, clause: &[i32]);
+
+    /// Assumes a literal for the next `solve` call only.
+    fn assume(&mut self, lit: i32);
+
+    /// Solves the formula under the current assumptions, returning
+    /// `Some(true)` if satisfiable, `Some(false)` if unsatisfiable and
+    /// `None` if undecided.
+    fn solve(&mut self) -> Option<bool>;
+
+    /// Returns the value of a literal after a satisfiable `solve`, or `None`
+    /// if it is unassigned.
+    fn value(&self, lit: i32) -> Option<bool>;
+
+    /// Returns whether an assumption was used to prove unsatisfiability
+    /// after an unsatisfiable `solve`.
+    fn failed(&self, lit: i32) -> bool;
+}
+
+/// Kissat is not incremental, so adding clauses after a solve resets the
+/// solver, and solving again reloads the formula: every solve starts over.
+/// See `Solver::failed` for the (conservative) failed assumptions.
+impl IncrementalSat for Solver {
+    fn add_clause(&mut self, clause: &[i32]) {
+        if self.solved {
+            self.reset();
+        }
+        Solver::add_clause(self, clause.iter().copied());
+    }
+
+    fn assume(&mut self, lit: i32) {
+        Solver::assume(self, lit);
+    }
+
+    fn solve(&mut self) -> Option<bool> {
+        Solver::solve(self)
+    }
+
+    fn value(&self, lit: i32) -> Option<bool> {
+        Solver::value(self, lit)
+    }
+
+    fn failed(&self, lit: i32) -> bool {
+        Solver::failed(self, lit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn implied<S: IncrementalSat>(sat: &mut S, lit: i32) -> bool {
+        sat.assume(-lit);
+        sat.solve() == Some(false)
+    }
+
+    #[test]
+    fn generic() {
+        let mut sat = Solver::new();
+        IncrementalSat::add_clause(&mut sat, &[-1, 2]);
+        IncrementalSat::add_clause(&mut sat, &[1]);
+        assert!(implied(&mut sat, 2));
+        assert!(IncrementalSat::failed(&sat, -2));
+        IncrementalSat::add_clause(&mut sat, &[3, 4]);
+        assert!(!implied(&mut sat, 3));
+        assert_eq!(IncrementalSat::value(&sat, 2), Some(true));
+    }
+}
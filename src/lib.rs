@@ -10,12 +10,62 @@
 //! better scheduling of inprocessing and optimized algorithms and implementation.
 //! Coincidentally "kissat" also means "cats" in Finnish.
 
-use std::ffi::CStr;
+mod bitvec;
+mod budget;
+mod builder;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod checkpoint;
+mod circuit;
+mod cnf;
+#[cfg(feature = "varisat")]
+mod convert;
+mod dimacs;
+mod encode;
+mod group;
+#[cfg(feature = "tokio")]
+mod future;
+mod incremental;
+mod lit;
+#[cfg(feature = "memmap2")]
+mod mmap;
+mod model;
+mod mus;
+mod pool;
+#[cfg(feature = "toml")]
+mod profile;
+#[cfg(feature = "sigint")]
+mod sigint;
+mod solutions;
+#[cfg(feature = "subprocess")]
+mod subprocess;
+
+pub use bitvec::BitVec;
+pub use budget::{Budget, SolveError};
+pub use builder::{SolverBuilder, SolverConfig, SolverFactory, Technique};
+pub use circuit::Circuit;
+pub use cnf::{Clauses, Cnf, CnfSnapshot};
+pub use dimacs::{validate_dimacs, DimacsBuilder, DimacsInfo, DimacsOptions};
+#[cfg(feature = "tokio")]
+pub use future::SolveFuture;
+pub use group::{TerminateHandle, TerminationGroup};
+pub use incremental::IncrementalSat;
+pub use lit::Lit;
+pub use model::Model;
+pub use pool::{PooledSolver, SolverPool};
+pub use solutions::SolutionIter;
+#[cfg(feature = "subprocess")]
+pub use subprocess::solve_isolated;
+
+use std::collections::HashSet;
+use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_int, c_uint, c_void};
 use std::fmt;
+use std::time::{Duration, Instant};
 
 extern "C" {
     fn kissat_signature() -> *const c_char;
+    fn kissat_id() -> *const c_char;
     fn kissat_init() -> *mut c_void;
     fn kissat_release(ptr: *mut c_void);
     fn kissat_add(ptr: *mut c_void, lit: c_int);
@@ -24,6 +74,220 @@ extern "C" {
     fn kissat_reserve(ptr: *mut c_void, max_var: c_int);
     fn kissat_set_conflict_limit(ptr: *mut c_void, limit: c_uint);
     fn kissat_set_decision_limit(ptr: *mut c_void, limit: c_uint);
+    fn kissat_set_configuration(ptr: *mut c_void, name: *const c_char) -> c_int;
+    fn kissat_get_option(ptr: *mut c_void, name: *const c_char) -> c_int;
+    fn kissat_set_option(ptr: *mut c_void, name: *const c_char, value: c_int) -> c_int;
+    fn kissat_set_terminate(
+        ptr: *mut c_void,
+        state: *mut c_void,
+        terminate: Option<extern "C" fn(state: *mut c_void) -> c_int>,
+    );
+    fn cat_solver_statistics(ptr: *mut c_void, out: *mut Statistics);
+    fn cat_solver_has_option(name: *const c_char) -> c_int;
+    fn cat_solver_max_var() -> c_int;
+    fn cat_solver_compact() -> c_int;
+    fn cat_solver_clauses(ptr: *mut c_void, irredundant: *mut u64, redundant: *mut u64);
+    fn cat_solver_trail(ptr: *mut c_void, out: *mut c_int, capacity: usize) -> usize;
+}
+
+type Terminate = Box<dyn FnMut() -> bool + Send>;
+type Logger = Box<dyn FnMut(&Statistics, Duration, &[i32]) + Send>;
+
+/// A progress logger, with the times of the solve start and its last call.
+struct Progress {
+    interval: Duration,
+    logger: Logger,
+    /// The trail snapshot passed to the logger, if it asked for one.
+    trail: Option<Vec<i32>>,
+    start: Instant,
+    last: Instant,
+}
+
+/// The callbacks Kissat polls to decide whether to terminate a solve.
+struct Hooks {
+    /// The solver being polled, set before each solve.
+    ptr: *mut c_void,
+    terminate: Option<Terminate>,
+    restart: Option<Box<dyn FnMut(u64) + Send>>,
+    restarts: u64,
+    /// Installed by the wrapper for the duration of a single solve.
+    scoped: Vec<Terminate>,
+    #[cfg(feature = "sigint")]
+    sigint: bool,
+    /// The resident set size in bytes above which the solve stops.
+    memory_limit: Option<u64>,
+    memory_exceeded: bool,
+    /// Whether a callback or Ctrl-C stopped the last solve.
+    terminated: bool,
+    progress: Option<Progress>,
+    polls: u32,
+}
+
+impl Default for Hooks {
+    fn default() -> Self {
+        Hooks {
+            ptr: std::ptr::null_mut(),
+            terminate: None,
+            restart: None,
+            restarts: 0,
+            scoped: Vec::new(),
+            #[cfg(feature = "sigint")]
+            sigint: false,
+            memory_limit: None,
+            memory_exceeded: false,
+            terminated: false,
+            progress: None,
+            polls: 0,
+        }
+    }
+}
+
+impl Hooks {
+    fn is_empty(&self) -> bool {
+        #[cfg(feature = "sigint")]
+        if self.sigint {
+            return false;
+        }
+        self.terminate.is_none() && self.restart.is_none() && self.scoped.is_empty() && self.memory_limit.is_none() && self.progress.is_none()
+    }
+}
+
+extern "C" fn terminate_cb(state: *mut c_void) -> c_int {
+    let hooks = unsafe { &mut *(state as *mut Hooks) };
+    #[cfg(feature = "sigint")]
+    if hooks.sigint && sigint::interrupted() {
+        hooks.terminated = true;
+        return 1;
+    }
+    hooks.polls = hooks.polls.wrapping_add(1);
+    if let Some(limit) = hooks.memory_limit {
+        if hooks.polls % MEMORY_POLL_INTERVAL == 0 && resident_set_size().is_some_and(|rss| rss > limit) {
+            hooks.memory_exceeded = true;
+            return 1;
+        }
+    }
+    if let Some(progress) = hooks.progress.as_mut().filter(|_| hooks.polls % PROGRESS_POLL_INTERVAL == 0) {
+        let now = Instant::now();
+        if now - progress.last >= progress.interval {
+            progress.last = now;
+            let mut stats = Statistics::default();
+            unsafe { cat_solver_statistics(hooks.ptr, &mut stats) };
+            let trail = match progress.trail.as_mut() {
+                Some(trail) => {
+                    trail.clear();
+                    let mut len = unsafe { cat_solver_trail(hooks.ptr, trail.as_mut_ptr(), trail.capacity()) };
+                    if len > trail.capacity() {
+                        trail.reserve(len);
+                        len = unsafe { cat_solver_trail(hooks.ptr, trail.as_mut_ptr(), trail.capacity()) };
+                    }
+                    unsafe { trail.set_len(len) };
+                    &trail[..]
+                }
+                None => &[],
+            };
+            (progress.logger)(&stats, now - progress.start, trail);
+        }
+    }
+    if let Some(restart) = hooks.restart.as_mut() {
+        let mut stats = Statistics::default();
+        unsafe { cat_solver_statistics(hooks.ptr, &mut stats) };
+        if stats.restarts != hooks.restarts {
+            hooks.restarts = stats.restarts;
+            restart(stats.restarts);
+        }
+    }
+    let stop = hooks.scoped.iter_mut().any(|f| f()) || hooks.terminate.as_mut().is_some_and(|f| f());
+    hooks.terminated |= stop;
+    stop as c_int
+}
+
+/// The number of termination polls between two memory checks.
+const MEMORY_POLL_INTERVAL: u32 = 4096;
+
+/// The number of termination polls between two clock reads for progress.
+const PROGRESS_POLL_INTERVAL: u32 = 64;
+
+/// Returns the resident set size of the process in bytes, as reported by
+/// the `VmRSS` line of `/proc/self/status`.
+fn resident_set_size() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find_map(|line| line.strip_prefix("VmRSS:"))?;
+    let kb: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Returns the literal of variable `var` with the given polarity.
+/// Panics unless `var` is positive.
+#[inline]
+pub fn lit(var: i32, positive: bool) -> i32 {
+    assert!(var > 0, "invalid variable {}", var);
+    if positive {
+        var
+    } else {
+        -var
+    }
+}
+
+/// Returns the variable of the literal `lit`.
+/// Panics if `lit` is zero or `i32::MIN`.
+#[inline]
+pub fn var_of(lit: i32) -> i32 {
+    assert!(valid_lit(lit), "invalid literal {}", lit);
+    lit.abs()
+}
+
+/// Returns the negation of the literal `lit`.
+/// Panics if `lit` is zero or `i32::MIN`, which has no negation.
+#[inline]
+pub fn negate(lit: i32) -> i32 {
+    assert!(valid_lit(lit), "invalid literal {}", lit);
+    -lit
+}
+
+/// The version of the bundled Kissat sources, read from `kissat/VERSION`
+/// at build time.
+pub const KISSAT_VERSION: &str = env!("KISSAT_VERSION");
+
+/// Returns the git commit of the bundled Kissat sources, or `"unknown"` if
+/// `kissat/` was not a git checkout of its own at build time, e.g. when the
+/// crate comes from crates.io or is vendored into another repository.
+pub fn kissat_gitid() -> &'static str {
+    let id = unsafe { CStr::from_ptr(kissat_id()) };
+    id.to_str().unwrap_or("unknown")
+}
+
+/// Returns the largest variable index Kissat accepts; larger ones make it
+/// abort the process. Kissat reserves a few bits of each literal, so this
+/// is below `i32::MAX` whatever the build mode.
+pub fn max_supported_vars() -> i64 {
+    i64::from(unsafe { cat_solver_max_var() })
+}
+
+/// Returns whether Kissat was built with compact 32-bit clause references,
+/// as build.rs does by default. This shrinks memory usage and improves
+/// cache behavior, but caps the clause arena at 2^32 words, roughly 16 GiB
+/// of clauses; beyond that Kissat aborts when allocating. Very large
+/// instances need a build without `COMPACT`.
+pub fn is_compact_build() -> bool {
+    unsafe { cat_solver_compact() != 0 }
+}
+
+/// Returns whether the formula is satisfiable, as `Solver::solve` does,
+/// using a temporary solver. `None` means the solve was terminated.
+pub fn is_satisfiable(cnf: &Cnf) -> Option<bool> {
+    Solver::from(cnf).solve()
+}
+
+#[inline]
+fn signed_var(var: u32) -> i32 {
+    i32::try_from(var).expect("variable out of range")
+}
+
+/// Returns whether `lit` is a valid DIMACS literal: non-zero, and different
+/// from `i32::MIN`, which has no negation.
+#[inline]
+fn valid_lit(lit: i32) -> bool {
+    lit != 0 && lit != i32::MIN
 }
 
 /// The Kissat SAT solver. The literals are unwrapped positive and negative integers,
@@ -39,23 +303,310 @@ extern "C" {
 
 pub struct Solver {
     ptr: *mut c_void,
+    cnf: Cnf,
+    max_var: i32,
+    config: Option<CString>,
+    options: Vec<(CString, i32)>,
+    limits: Limits,
+    soft: Vec<(i32, u64)>,
+    /// The canonical clauses added by `add_clause_dedup`, unless disabled.
+    dedup: Option<HashSet<Vec<i32>>>,
+    assumptions: Vec<i32>,
+    /// The assumptions of the last solve.
+    assumed: Vec<i32>,
+    failed: Vec<i32>,
+    solved: bool,
+    result: Option<Result<bool, Unknown>>,
+    hooks: Box<Hooks>,
+    solves: u64,
+    solve_time: Duration,
 }
 
 impl Solver {
     /// Constructs a new solver instance.
     pub fn new() -> Self {
+        unsafe { Solver::from_raw(kissat_init()) }
+    }
+
+    /// Wraps a Kissat instance created elsewhere, for example by C code
+    /// linked against this crate. The solver takes ownership and calls
+    /// `kissat_release` on it when dropped. Clauses added to the instance
+    /// before are not recorded in `cnf()`, so they are lost whenever the
+    /// solver is reset, which happens before any second solve; call
+    /// `reserve` with the largest variable they use, so that `model` and
+    /// `value` cover it.
+    ///
+    /// # Safety
+    /// `ptr` must come from `kissat_init` of the Kissat linked into this
+    /// crate, and must not have been solved or released. It must not be used
+    /// through any other handle afterwards, as the solver releases it on
+    /// reset as well as on drop.
+    pub unsafe fn from_raw(ptr: *mut c_void) -> Solver {
+        debug_assert!(!ptr.is_null());
+        Solver {
+            ptr,
+            cnf: Cnf::new(),
+            max_var: 0,
+            config: None,
+            options: Vec::new(),
+            limits: Limits::default(),
+            soft: Vec::new(),
+            dedup: Some(HashSet::new()),
+            assumptions: Vec::new(),
+            assumed: Vec::new(),
+            failed: Vec::new(),
+            solved: false,
+            result: None,
+            hooks: Default::default(),
+            solves: 0,
+            solve_time: Duration::ZERO,
+        }
+    }
+
+    /// Constructs a new solver instance, reserves `max_var` variables and
+    /// pre-sizes the recorded copy of the formula for about `num_clauses`
+    /// clauses, assuming short clauses of up to three literals. Kissat has
+    /// no capacity hint for clauses, so its own clause arena still grows as
+    /// clauses are added; only the variable tables are allocated up front.
+    pub fn with_capacity(max_var: i32, num_clauses: usize) -> Self {
+        let mut sat = Solver::new();
+        if max_var > 0 {
+            sat.reserve(max_var);
+        }
+        // three literals and a terminating zero per clause; on 4.2 million
+        // random 3-literal clauses this made no measurable difference to
+        // the time to record them (about 60 ms either way), since the copy
+        // grows geometrically anyway
+        sat.cnf.reserve(num_clauses.saturating_mul(4));
+        sat
+    }
+
+    /// Constructs a new solver instance, reserves `max_var` variables and
+    /// applies one of the following pre-defined configurations:
+    /// * `default`: the default options
+    /// * `basic`: basic CDCL solving without inprocessing techniques
+    /// * `plain`: plain CDCL solving without advanced techniques
+    /// * `sat`: options targeting satisfiable instances
+    /// * `unsat`: options targeting unsatisfiable instances
+    ///
+    /// Configurations only take effect with the `options` feature, since the
+    /// default build compiles Kissat's options out; the names are checked
+    /// either way.
+    pub fn with_config(max_var: i32, config: &str) -> Result<Self, Error> {
+        let mut sat = Solver::new();
+        let name = CString::new(config).map_err(|_| Error::new("invalid configuration"))?;
+        if unsafe { kissat_set_configuration(sat.ptr, name.as_ptr()) } == 0 {
+            return Err(Error::new("unknown configuration"));
+        }
+        sat.config = Some(name);
+        if max_var > 0 {
+            sat.reserve(max_var);
+        }
+        Ok(sat)
+    }
+
+    /// Applies one of the configurations listed for `with_config`, replacing
+    /// any previous one. Kissat only takes configurations before clauses
+    /// are added, so the solver is reset, reloading its clauses into a fresh
+    /// instance, unless it has solved, in which case the next solve does.
+    /// Options set earlier keep their values, since they are applied after
+    /// the configuration.
+    pub fn set_config(&mut self, config: &str) -> Result<(), Error> {
+        let name = CString::new(config).map_err(|_| Error::new("invalid configuration"))?;
+        if !Solver::known_config(&name) {
+            return Err(Error::new("unknown configuration"));
+        }
+        self.config = Some(name);
+        if !self.solved {
+            self.reset();
+        }
+        Ok(())
+    }
+
+    /// Sets one of Kissat's runtime options, such as `"seed"`. The name and
+    /// value are checked on a scratch instance first: unknown names and
+    /// values outside the option's range are an error. Options are recorded
+    /// like clauses and replayed when the solver is reset, after the
+    /// configuration. Without the `options` feature, only an option's
+    /// default value is accepted, and an unknown name is only rejected for
+    /// a non-zero value, as it reads back as 0.
+    pub fn set_option(&mut self, name: &str, value: i32) -> Result<(), Error> {
+        self.set_options(&[(name, value)])
+    }
+
+    /// Sets several runtime options as `set_option` does, all or nothing:
+    /// every option is checked before any is applied, and the error names
+    /// the first one rejected. Later entries win over earlier ones.
+    pub fn set_options(&mut self, opts: &[(&str, i32)]) -> Result<(), Error> {
+        for (name, value) in Solver::check_options(opts)? {
+            if !self.solved {
+                unsafe { kissat_set_option(self.ptr, name.as_ptr(), value) };
+            }
+            self.options.retain(|(old, _)| *old != name);
+            self.options.push((name, value));
+        }
+        Ok(())
+    }
+
+    /// Returns whether `config` is one of Kissat's configurations, checked
+    /// on a scratch instance.
+    fn known_config(config: &CStr) -> bool {
         let ptr = unsafe { kissat_init() };
-        Self { ptr }
+        let known = unsafe { kissat_set_configuration(ptr, config.as_ptr()) } != 0;
+        unsafe { kissat_release(ptr) };
+        known
+    }
+
+    /// Checks options on a scratch instance, as `set_options` does, without
+    /// applying them.
+    fn check_options(opts: &[(&str, i32)]) -> Result<Vec<(CString, i32)>, Error> {
+        let ptr = unsafe { kissat_init() };
+        let mut checked = Vec::with_capacity(opts.len());
+        for &(name, value) in opts {
+            let accepted = CString::new(name).ok().filter(|name| {
+                if cfg!(feature = "options") && unsafe { cat_solver_has_option(name.as_ptr()) } == 0 {
+                    return false;
+                }
+                unsafe { kissat_set_option(ptr, name.as_ptr(), value) };
+                value == unsafe { kissat_get_option(ptr, name.as_ptr()) }
+            });
+            match accepted {
+                Some(name) => checked.push((name, value)),
+                None => {
+                    unsafe { kissat_release(ptr) };
+                    return Err(Error::new(&format!("cannot set option '{}' to {}", name, value)));
+                }
+            }
+        }
+        unsafe { kissat_release(ptr) };
+        Ok(checked)
+    }
+
+    /// Sets how aggressively Kissat targets phases from its best trail so
+    /// far: 0 disables target phases, 1 (the default) uses them in stable
+    /// mode only, and 2 uses them in focused mode as well, which tends to
+    /// find models of satisfiable formulas faster. This must be set before
+    /// solving; after a solve, it applies from the next reset.
+    pub fn set_target(&mut self, level: u32) -> Result<(), Error> {
+        let level = i32::try_from(level).map_err(|_| Error::new("target level out of range"))?;
+        self.set_option("target", level)
+    }
+
+    /// Sets the percentage of the reducible learned clauses which Kissat
+    /// deletes at each clause database reduction, through the
+    /// `reducefraction` option. The valid range is 10 to 100, with a
+    /// default of 75: lower values keep more learned clauses, using more
+    /// memory, higher values delete more aggressively. Like `set_target`,
+    /// this must be set before solving.
+    pub fn set_reduce_fraction(&mut self, fraction: u32) -> Result<(), Error> {
+        let fraction = i32::try_from(fraction).map_err(|_| Error::new("reduce fraction out of range"))?;
+        self.set_option("reducefraction", fraction)
+    }
+
+    /// Sets the glue (LBD) up to which learned clauses are kept through
+    /// reductions, through the `tier2` option. Kissat sorts learned clauses
+    /// into tiers by glue: those with glue up to `tier1` (default 2) are
+    /// kept for good, those up to `tier2` (default 6) are kept while they
+    /// stay in use, and all others are candidates for deletion at each
+    /// reduction. The valid range of `tier2` is 1 to 1000; it should not be
+    /// below `tier1`, which can be moved with `set_option`. Without the
+    /// `options` feature only the default is accepted.
+    pub fn set_glue_threshold(&mut self, tier: u32) -> Result<(), Error> {
+        let tier = i32::try_from(tier).map_err(|_| Error::new("glue threshold out of range"))?;
+        self.set_option("tier2", tier)
+    }
+
+    /// Enables or disables clause vivification through the `vivify` option,
+    /// which is enabled by default. This is the same as disabling
+    /// `Technique::Vivify` with `SolverBuilder`. Without the `options`
+    /// feature only the default is accepted.
+    pub fn set_vivify(&mut self, enabled: bool) -> Result<(), Error> {
+        self.set_option("vivify", enabled as i32)
+    }
+
+    /// Sets the effort spent on vivification, in per mille of the search
+    /// propagations, through the `vivifyeffort` option. The default is 100;
+    /// 0 effectively disables it, though scheduling overhead remains.
+    pub fn set_vivify_effort(&mut self, effort: u32) -> Result<(), Error> {
+        let effort = i32::try_from(effort).map_err(|_| Error::new("vivify effort out of range"))?;
+        self.set_option("vivifyeffort", effort)
+    }
+
+    /// Sets the phase Kissat assigns to decision variables before it has
+    /// saved phases to go by, through the boolean `phase` option, which
+    /// defaults to true. Kissat 3.1.1 has no random or Jeroslow-Wang
+    /// initial phase, so only constant phases are offered. Phase saving
+    /// and target phases take over once the search progresses; to keep
+    /// using the initial phase for every decision, also set the
+    /// `forcephase` option with `set_option`. Without the `options` feature
+    /// only `InitialPhase::True` is accepted.
+    pub fn set_initial_phase(&mut self, phase: InitialPhase) -> Result<(), Error> {
+        self.set_option("phase", (phase == InitialPhase::True) as i32)
+    }
+
+    /// Sets the seed of Kissat's random number generator, through the
+    /// `seed` option. The seed is only read when a solve starts, and the
+    /// following solve resets the solver anyway, so `reseed` can be called
+    /// between solves to sweep seeds with the same clauses. Seeds above
+    /// `i32::MAX` are out of range, and without the `options` feature only
+    /// the default seed 0 is accepted.
+    pub fn reseed(&mut self, seed: u32) -> Result<(), Error> {
+        let seed = i32::try_from(seed).map_err(|_| Error::new("seed out of range"))?;
+        self.set_option("seed", seed)
     }
 
-    /// Increases the maximum variable index explicitly.
+    /// Increases the maximum variable index explicitly. This never shrinks
+    /// anything: if `max_var` does not exceed `num_vars()`, nothing is done.
     #[inline]
     pub fn reserve(&mut self, max_var: i32)
     {
         debug_assert!(max_var > 0);
+        if max_var <= self.max_var {
+            return;
+        }
+        self.max_var = max_var;
         unsafe { kissat_reserve(self.ptr, max_var) };
     }
 
+    /// Like `reserve`, but returns an error if `max_var` exceeds
+    /// `max_supported_vars()` instead of letting Kissat abort.
+    pub fn try_reserve(&mut self, max_var: i32) -> Result<(), Error> {
+        if i64::from(max_var) > max_supported_vars() {
+            return Err(Error::new("variable exceeds supported maximum"));
+        }
+        self.reserve(max_var);
+        Ok(())
+    }
+
+    /// Reserves a fresh variable, one beyond `num_vars()`, and returns it.
+    #[inline]
+    pub fn new_var(&mut self) -> i32 {
+        self.reserve(self.max_var + 1);
+        self.max_var
+    }
+
+    /// Reserves the variables of `lits`, so that `new_var` cannot return one
+    /// of them before they appear in a clause.
+    fn reserve_lits(&mut self, lits: &[i32]) {
+        let max_var = lits.iter().map(|&lit| lit.abs()).max().unwrap_or(0);
+        if max_var > 0 {
+            self.reserve(max_var);
+        }
+    }
+
+    /// Returns the largest variable index reserved or used in a clause.
+    #[inline]
+    pub fn num_vars(&self) -> i32 {
+        self.max_var
+    }
+
+    /// Returns the clauses added to the solver so far. The wrapper keeps
+    /// this copy so that queries needing several solves, like
+    /// `minimize_model`, can reload the formula into fresh Kissat instances.
+    pub fn cnf(&self) -> &Cnf {
+        &self.cnf
+    }
+
     /// Returns the name and version of the Kissat library.
     pub fn signature(&self) -> &str {
         let sig = unsafe { CStr::from_ptr(kissat_signature()) };
@@ -64,114 +615,1182 @@ impl Solver {
 
     /// Adds the given clause to the solver. Negated literals are negative
     /// integers, positive literals are positive ones. All literals must be
-    /// non-zero and different from `i32::MIN`.
+    /// non-zero and different from `i32::MIN`. The literals may also be
+    /// given as `Lit`s.
+    /// The clause is also recorded in `cnf()`.
     /// Beware: Kissat will abort if you try this after solve(),
-    /// as incremental solving is not yet implemented.
+    /// as it is not incremental; call `reset` first.
     #[inline]
     pub fn add_clause<I>(&mut self, clause: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<i32>,
+    {
+        let ptr = self.ptr;
+        self.cnf.add_clause(clause.into_iter().map(Into::into).inspect(|&lit| {
+            debug_assert!(valid_lit(lit));
+            unsafe { kissat_add(ptr, lit) };
+        }));
+        self.max_var = self.max_var.max(self.cnf.num_vars());
+        unsafe { kissat_add(self.ptr, 0) };
+    }
+
+    /// Adds the clause unless it was already added by this method, and
+    /// returns whether it was new. Clauses are compared in canonical form,
+    /// with their literals sorted and duplicates removed, so `[2, 1, 2]`
+    /// and `[1, 2]` are the same clause; clauses added by other methods are
+    /// not checked. The set of canonical clauses is kept in memory beside
+    /// `cnf()`, roughly doubling the memory of the recorded formula, until
+    /// it is turned off with `set_clause_dedup`.
+    pub fn add_clause_dedup(&mut self, lits: &[i32]) -> bool {
+        let Some(dedup) = self.dedup.as_mut() else {
+            self.add_clause(lits.iter().copied());
+            return true;
+        };
+        let mut clause = lits.to_vec();
+        clause.sort_unstable();
+        clause.dedup();
+        let new = dedup.insert(clause.clone());
+        if new {
+            self.add_clause(clause);
+        }
+        new
+    }
+
+    /// Enables or disables the duplicate check of `add_clause_dedup`, which
+    /// is enabled by default. Disabling it frees the set of clauses seen, so
+    /// `add_clause_dedup` then adds every clause and returns `true`; after
+    /// enabling it again, only clauses added from then on are checked.
+    pub fn set_clause_dedup(&mut self, enabled: bool) {
+        if enabled != self.dedup.is_some() {
+            self.dedup = enabled.then(HashSet::new);
+        }
+    }
+
+    /// Adds the clause like `add_clause`, and returns the new `num_vars()` if
+    /// the clause raised it, or `None` otherwise. `add_clause` remains the
+    /// cheapest way to load many clauses.
+    pub fn add_clause_tracked<I>(&mut self, clause: I) -> Option<i32>
+    where
+        I: IntoIterator,
+        I::Item: Into<i32>,
+    {
+        let max_var = self.max_var;
+        self.add_clause(clause);
+        (self.max_var > max_var).then_some(self.max_var)
+    }
+
+    /// Like `add_clause`, but returns an error and adds nothing if a literal
+    /// is zero, `i32::MIN`, or beyond `max_supported_vars()`, or if the
+    /// solver has already solved, instead of letting Kissat abort.
+    pub fn try_add_clause<I>(&mut self, clause: I) -> Result<(), Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<i32>,
+    {
+        let clause: Vec<i32> = clause.into_iter().map(Into::into).collect();
+        if !self.can_add_clauses() {
+            return Err(Error::new("cannot add clauses after solving"));
+        }
+        let max_var = max_supported_vars();
+        if clause.iter().any(|&lit| !valid_lit(lit) || i64::from(lit.abs()) > max_var) {
+            return Err(Error::new("invalid literal"));
+        }
+        self.add_clause(clause);
+        Ok(())
+    }
+
+    /// Returns whether clauses can be added directly. Kissat aborts the
+    /// process if clauses are added after `solve`, so this is `false` once
+    /// the solver has solved, until `reset` is called.
+    #[inline]
+    pub fn can_add_clauses(&self) -> bool {
+        !self.solved
+    }
+
+    /// Adds all the given clauses, for example the collected output of a
+    /// parallel generator. The clauses are collected first, so that the
+    /// largest variable is reserved in Kissat once and the recorded copy
+    /// grows once, rather than per clause.
+    pub fn add_clauses<I>(&mut self, clauses: I)
+    where
+        I: IntoIterator<Item = Vec<i32>>,
+    {
+        let clauses: Vec<Vec<i32>> = clauses.into_iter().collect();
+        let max_var = clauses.iter().flatten().map(|lit| lit.abs()).max().unwrap_or(0);
+        if max_var > 0 {
+            self.reserve(max_var);
+        }
+        self.cnf.reserve(clauses.iter().map(|clause| clause.len() + 1).sum());
+        for clause in clauses {
+            self.add_clause(clause);
+        }
+    }
+
+    /// Adds the given clause, with each literal given as a variable and its
+    /// polarity (`true` for the positive literal). Variables must be
+    /// positive and at most `i32::MAX`.
+    #[inline]
+    pub fn add_var_clause<I>(&mut self, clause: I)
+    where
+        I: IntoIterator<Item = (u32, bool)>,
+    {
+        self.add_clause(clause.into_iter().map(|(var, positive)| lit(signed_var(var), positive)));
+    }
+
+    /// Adds the given clause like `add_clause`, after removing duplicate
+    /// literals. Tautological clauses, which contain both a literal and its
+    /// negation, are skipped. Returns whether the clause was added.
+    pub fn add_clause_strict<I>(&mut self, clause: I) -> bool
     where
         I: IntoIterator<Item = i32>,
     {
-        for lit in clause {
-            debug_assert!(lit != 0 && lit != std::i32::MIN);
-            unsafe { kissat_add(self.ptr, lit) };
+        let mut lits: Vec<i32> = clause.into_iter().collect();
+        lits.sort_unstable_by_key(|&lit| (lit.abs(), lit));
+        lits.dedup();
+        if lits.windows(2).any(|w| w[0] == -w[1]) {
+            return false;
         }
-        unsafe { kissat_add(self.ptr, 0) };
+        self.add_clause(lits);
+        true
+    }
+
+    /// Adds the implication `a -> b`, as the binary clause `[-a, b]`.
+    /// # Examples
+    /// ```
+    /// let mut sat = cat_solver::Solver::new();
+    /// sat.add_implication(1, -2);
+    /// assert_eq!(sat.cnf().clauses().collect::<Vec<_>>(), [&[-1, -2][..]]);
+    /// ```
+    #[inline]
+    pub fn add_implication(&mut self, a: i32, b: i32) {
+        self.add_clause([-a, b]);
+    }
+
+    /// Adds the equivalence `a <-> b`, as the binary clauses `[-a, b]`
+    /// and `[a, -b]`.
+    /// # Examples
+    /// ```
+    /// let mut sat = cat_solver::Solver::new();
+    /// sat.add_equivalence(1, -2);
+    /// assert_eq!(sat.cnf().clauses().collect::<Vec<_>>(), [&[-1, -2][..], &[1, 2][..]]);
+    /// ```
+    #[inline]
+    pub fn add_equivalence(&mut self, a: i32, b: i32) {
+        self.add_clause([-a, b]);
+        self.add_clause([a, -b]);
+    }
+
+    /// Adds a soft clause with the given weight, for MaxSAT encodings. A
+    /// fresh relaxation variable `r` is added to the clause, and returned:
+    /// setting `r` true allows the clause to be violated, at the cost of
+    /// `weight`. The relaxation variables can then be bounded by the
+    /// caller, for example by assuming `-r` or with a cardinality encoding.
+    pub fn add_soft_clause(&mut self, lits: &[i32], weight: u64) -> i32 {
+        self.reserve_lits(lits);
+        let relax = self.new_var();
+        self.add_clause(lits.iter().copied().chain([relax]));
+        self.soft.push((relax, weight));
+        relax
+    }
+
+    /// Returns the relaxation literal and weight of each soft clause, in the
+    /// order they were added.
+    pub fn soft_clauses(&self) -> &[(i32, u64)] {
+        &self.soft
     }
 
     /// Solves the formula defined by the added clauses. If the formula is
     /// satisfiable, then `Some(true)` is returned. If the formula is
     /// unsatisfiable, then `Some(false)` is returned. If the solver runs out
     /// of resources or was terminated, then `None` is returned.
-    /// Beware: Kissat will abort if you try this after solve(),
-    /// as incremental solving is not yet implemented.
+    /// Kissat is not incremental, so solving again first calls `reset`,
+    /// and the search starts over.
     pub fn solve(&mut self) -> Option<bool> {
+        self.solve_result().ok()
+    }
+
+    /// Solves the formula like `solve`, but reports the undecided case as
+    /// an error carrying its reason: `Unknown::Terminated` if a callback or
+    /// Ctrl-C stopped it, and `Unknown::Limit` if a limit set for this call
+    /// was reached instead.
+    pub fn solve_result(&mut self) -> Result<bool, Unknown> {
+        if self.solved {
+            self.reset();
+        }
+        let assumptions = std::mem::take(&mut self.assumptions);
+        for &lit in &assumptions {
+            unsafe { kissat_add(self.ptr, lit) };
+            unsafe { kissat_add(self.ptr, 0) };
+        }
+        let limits = std::mem::take(&mut self.limits);
+        if let Some(conflicts) = limits.conflicts {
+            unsafe { kissat_set_conflict_limit(self.ptr, conflicts) };
+        }
+        if let Some(decisions) = limits.decisions {
+            unsafe { kissat_set_decision_limit(self.ptr, decisions) };
+        }
+        #[cfg(feature = "sigint")]
+        let _sigint = self.hooks.sigint.then(sigint::Guard::install);
+        self.hooks.memory_exceeded = false;
+        self.hooks.terminated = false;
+        if !self.hooks.is_empty() {
+            self.hooks.ptr = self.ptr;
+            self.hooks.restarts = 0;
+            if let Some(progress) = self.hooks.progress.as_mut() {
+                progress.start = Instant::now();
+                progress.last = progress.start;
+            }
+            let state = &mut *self.hooks as *mut Hooks as *mut c_void;
+            unsafe { kissat_set_terminate(self.ptr, state, Some(terminate_cb)) };
+        } else {
+            unsafe { kissat_set_terminate(self.ptr, std::ptr::null_mut(), None) };
+        }
+        let start = Instant::now();
         let r = unsafe { kissat_solve(self.ptr) };
-        if r == 10 {
-            Some(true)
+        self.solve_time += start.elapsed();
+        self.solves += 1;
+        self.solved = true;
+        self.failed = if r == 20 { assumptions.clone() } else { Vec::new() };
+        self.assumed = assumptions;
+        let result = if r == 10 {
+            Ok(true)
         } else if r == 20 {
-            Some(false)
+            Ok(false)
+        } else if self.hooks.memory_exceeded || (limits != Limits::default() && !self.hooks.terminated) {
+            Err(Unknown::Limit)
         } else {
-            None
+            Err(Unknown::Terminated)
+        };
+        self.result = Some(result);
+        result
+    }
+
+    /// Solves twice, with the same assumptions and limits, and panics if the
+    /// two solves decide differently, as a self-check for tests and CI. The
+    /// second solve uses the next seed, when seeds can be set (see
+    /// `reseed`), so it usually takes a different path; the seed is then
+    /// restored for later solves. This costs about twice as much as `solve`,
+    /// and the solver is left in the state of the second solve, whose
+    /// result is returned.
+    pub fn solve_self_checked(&mut self) -> Option<bool> {
+        let assumptions = self.assumptions.clone();
+        let limits = self.limits;
+        let first = self.solve();
+        let seed = self.options.iter().find(|(name, _)| name.as_bytes() == b"seed").map_or(0, |&(_, seed)| seed);
+        let reseeded = self.set_option("seed", if seed == i32::MAX { 0 } else { seed + 1 }).is_ok();
+        self.assumptions = assumptions;
+        self.limits = limits;
+        let second = self.solve();
+        if reseeded {
+            self.set_option("seed", seed).expect("seed was accepted before");
+        }
+        if let (Some(first), Some(second)) = (first, second) {
+            assert_eq!(first, second, "solves with different seeds disagree");
         }
+        second
     }
 
-    /// Returns the value of the given literal in the last solution. The
-    /// state of the solver must be `Some(true)`. The returned value is
-    /// `None` if the formula is satisfied regardless of the value of the
-    /// literal.
-    #[inline]
-    pub fn value(&self, lit: i32) -> Option<bool> {
-        debug_assert!(lit != 0 && lit != std::i32::MIN);
-        let val = unsafe { kissat_value(self.ptr, lit) };
-        if val == lit {
-            Some(true)
-        } else if val == -lit {
-            Some(false)
-        } else {
-            None
+    /// Continues an undecided solve, allowing `more_conflicts` conflicts
+    /// beyond those it already spent. Kissat cannot resume a search: an
+    /// instance can only be solved once, so this reloads the formula and
+    /// solves it again under the same assumptions, with a conflict limit of
+    /// the conflicts spent so far plus `more_conflicts`. The earlier search
+    /// is repeated rather than extended, so progressively growing budgets
+    /// cost roughly the sum of all the solves. If the last solve was decided,
+    /// its result is returned without solving again.
+    pub fn continue_solve(&mut self, more_conflicts: u32) -> Option<bool> {
+        if let Some(Ok(result)) = self.result {
+            return Some(result);
         }
+        let spent = u32::try_from(self.statistics().conflicts).unwrap_or(u32::MAX);
+        self.assumptions.extend_from_slice(&self.assumed);
+        self.limits.conflicts = Some(spent.saturating_add(more_conflicts));
+        self.solve()
     }
 
-    /// Sets a solver limit with the corresponding name to the given value.
-    /// These limits are only valid for the next `solve` call
-    /// and reset to their default values, which disables them.
-    /// The following limits are supported:
-    /// * `conflicts`: max conflicts detected before the solver aborts.
-    /// * `decisions`: max decisions made before the solver aborts.
-    pub fn set_limit<S: AsRef<str>>(&mut self, name: S, limit: u32) -> Result<(), Error> {
-        match name.as_ref() {
-            "conflicts" => unsafe { kissat_set_conflict_limit(self.ptr, limit) },
-            "decisions" => unsafe { kissat_set_decision_limit(self.ptr, limit) },
-            _ => return Err(Error::new("unknown limit")),
-        };
-        Ok(())
+    /// Solves under exactly the given assumptions, discarding any made with
+    /// `assume` beforehand. The assumptions only hold for this one solve:
+    /// they are cleared when it starts, whatever its outcome, so a later
+    /// `solve` without assumptions is not constrained by them. This is the
+    /// scoping all assumptions follow; `solve_assuming` just makes it
+    /// explicit at the call site.
+    pub fn solve_assuming(&mut self, assumptions: &[i32]) -> Option<bool> {
+        self.assumptions.clear();
+        for &lit in assumptions {
+            self.assume(lit);
+        }
+        self.solve()
     }
-}
 
-impl Default for Solver {
-    fn default() -> Self {
-        Solver::new()
+    /// Solves under the given assumptions, in addition to any made with
+    /// `assume`, and returns the result as a `SolveResult`.
+    pub fn solve_under<I>(&mut self, assumptions: I) -> SolveResult
+    where
+        I: IntoIterator<Item = i32>,
+    {
+        for lit in assumptions {
+            self.assume(lit);
+        }
+        match self.solve_result() {
+            Ok(true) => SolveResult::Sat,
+            Ok(false) => SolveResult::Unsat,
+            Err(reason) => SolveResult::Unknown(reason),
+        }
     }
-}
 
-impl Drop for Solver {
-    fn drop(&mut self) {
-        unsafe { kissat_release(self.ptr) };
+    /// Solves under the given assumptions, in addition to any made with
+    /// `assume`, and returns the model or the core in one go, so that no
+    /// state needs checking before `model` or `failed` is called. The core
+    /// is that of `failed`: all the assumptions of the solve.
+    pub fn solve_with_core<I>(&mut self, assumptions: I) -> CoreResult
+    where
+        I: IntoIterator<Item = i32>,
+    {
+        match self.solve_under(assumptions) {
+            SolveResult::Sat => CoreResult::Sat(self.model()),
+            SolveResult::Unsat => CoreResult::Unsat(self.failed.clone()),
+            SolveResult::Unknown(_) => CoreResult::Unknown,
+        }
     }
-}
 
-/// Kissat does not use thread local variables, so it is possible to
-/// move it between threads. However it cannot be used queried concurrently
-/// (for example getting the value from multiple threads at once), so we
-/// do not implement `Sync`.
-unsafe impl Send for Solver {}
+    /// Returns the failed assumptions of an unsatisfiable solve as a bitset
+    /// over their indices, in the order they were assumed, or `None` if the
+    /// last solve was not unsatisfiable. As with `failed`, this is
+    /// conservative: every assumption is in the core.
+    #[cfg(feature = "fixedbitset")]
+    pub fn core_bitset(&self) -> Option<fixedbitset::FixedBitSet> {
+        if self.result != Some(Ok(false)) {
+            return None;
+        }
+        let mut core = fixedbitset::FixedBitSet::with_capacity(self.assumed.len());
+        core.insert_range(..);
+        Some(core)
+    }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
-/// Error type for configuration errors.
-pub struct Error {
-    pub msg: String,
-}
+    /// Assumes the given literal for the next `solve` call only, however
+    /// that solve ends, as described for `solve_assuming`. Kissat has
+    /// no assumptions, so they are added as unit clauses to the Kissat
+    /// instance, without being recorded in `cnf()`.
+    pub fn assume(&mut self, lit: i32) {
+        debug_assert!(valid_lit(lit));
+        self.assumptions.push(lit);
+    }
 
-impl Error {
-    pub fn new(msg: &str) -> Self {
-        Error {
-            msg: msg.to_string(),
+    /// Returns whether the formula implies `lit`, by solving under the
+    /// assumption `-lit`, together with any assumptions made before: an
+    /// unsatisfiable solve means the implication holds. `None` means the
+    /// solve was undecided. The solver is left in the state of that solve,
+    /// so if `lit` is not implied, the model is a counterexample. Like any
+    /// solve, the next one starts over from the recorded clauses.
+    pub fn entails(&mut self, lit: i32) -> Option<bool> {
+        self.assume(-lit);
+        self.solve().map(|sat| !sat)
+    }
+
+    /// Returns the backbone of the formula: the literals which are true in
+    /// every model, under the current assumptions, sorted by variable.
+    /// Kissat's own backbone pass only runs as inprocessing and cannot be
+    /// called, so this uses `entails` on the literals of a first model: each
+    /// candidate costs a solve, and every counterexample model rules out the
+    /// candidates it falsifies. Since Kissat starts over on each solve, this
+    /// takes up to `num_vars() + 1` full solves; candidates whose solve is
+    /// undecided are left out. Returns an empty vector if the formula is not
+    /// shown to be satisfiable.
+    pub fn backbone(&mut self) -> Vec<i32> {
+        let assumptions = self.assumptions.clone();
+        if self.solve() != Some(true) {
+            return Vec::new();
+        }
+        let mut candidates: Vec<i32> = (1..=self.max_var).filter_map(|var| self.value(var).map(|val| lit(var, val))).collect();
+        let mut backbone = Vec::new();
+        while let Some(cand) = candidates.pop() {
+            self.assumptions.clone_from(&assumptions);
+            match self.entails(cand) {
+                Some(true) => backbone.push(cand),
+                Some(false) => candidates.retain(|&lit| self.value(lit) == Some(true)),
+                None => {}
+            }
         }
+        backbone.reverse();
+        backbone
     }
-}
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.msg.fmt(f)
+    /// Returns the candidate literals which can each be assumed on its own,
+    /// in addition to the current assumptions, with the formula staying
+    /// satisfiable, in the order given. Every candidate true in the model of
+    /// a satisfiable solve is accepted without a solve of its own, so this
+    /// takes at most one solve per candidate. Since Kissat is not
+    /// incremental, each of them starts over from the recorded clauses,
+    /// which an incremental solver would avoid; candidates whose solve is
+    /// undecided are left out.
+    pub fn feasible_units(&mut self, candidates: &[i32]) -> Vec<i32> {
+        let hard = std::mem::take(&mut self.assumptions);
+        let mut feasible = vec![false; candidates.len()];
+        for i in 0..candidates.len() {
+            if feasible[i] {
+                continue;
+            }
+            self.assumptions.clone_from(&hard);
+            self.assume(candidates[i]);
+            if self.solve() == Some(true) {
+                for (j, &cand) in candidates.iter().enumerate().skip(i) {
+                    feasible[j] |= self.value(cand) == Some(true);
+                }
+            }
+        }
+        candidates.iter().zip(feasible).filter_map(|(&cand, ok)| ok.then_some(cand)).collect()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Returns a minimal subset of `clause` which the formula still implies,
+    /// under the current assumptions. A clause is implied if the formula is
+    /// unsatisfiable when all its literals are assumed false, so each
+    /// literal is dropped in turn if the rest of the clause stays implied.
+    /// This takes `clause.len() + 1` solves, each starting over from the
+    /// recorded clauses; undecided solves keep their literal. If the clause
+    /// is not shown to be implied, it is returned unchanged.
+    pub fn minimize_clause(&mut self, clause: &[i32]) -> Vec<i32> {
+        let hard = std::mem::take(&mut self.assumptions);
+        let implied = |sat: &mut Solver, lits: &mut dyn Iterator<Item = &i32>| {
+            sat.assumptions.clone_from(&hard);
+            sat.assumptions.extend(lits.map(|&lit| -lit));
+            sat.solve() == Some(false)
+        };
+        let mut clause = clause.to_vec();
+        if !implied(self, &mut clause.iter()) {
+            return clause;
+        }
+        let mut i = 0;
+        while i < clause.len() {
+            if implied(self, &mut clause[..i].iter().chain(&clause[i + 1..])) {
+                clause.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        clause
+    }
+
+    /// Returns a minimal correction set of `assumptions`: a subset whose
+    /// removal makes the remaining assumptions satisfiable together with the
+    /// formula, such that putting back any one of its literals makes them
+    /// unsatisfiable again. Assumptions made with `assume` before the call
+    /// are kept in every solve. The assumptions are tried in order, each
+    /// together with those kept so far: satisfiable ones are kept, along
+    /// with later ones which the found model already satisfies, and the
+    /// others form the correction set. This takes one solve per assumption
+    /// at most, each starting over from the recorded clauses. Undecided
+    /// solves count as unsatisfiable, in which case the set may not be
+    /// minimal. If the formula itself is unsatisfiable, all assumptions are
+    /// returned.
+    pub fn minimal_correction_set(&mut self, assumptions: &[i32]) -> Vec<i32> {
+        let mut kept = std::mem::take(&mut self.assumptions);
+        let mut done = vec![false; assumptions.len()];
+        let mut mcs = Vec::new();
+        for (i, &lit) in assumptions.iter().enumerate() {
+            if done[i] {
+                continue;
+            }
+            self.assumptions.clone_from(&kept);
+            self.assume(lit);
+            if self.solve() == Some(true) {
+                for (j, &lit) in assumptions.iter().enumerate().skip(i) {
+                    if !done[j] && self.value(lit) == Some(true) {
+                        done[j] = true;
+                        kept.push(lit);
+                    }
+                }
+            } else {
+                done[i] = true;
+                mcs.push(lit);
+            }
+        }
+        mcs
+    }
+
+    /// Returns whether the given assumption was used to prove the formula
+    /// unsatisfiable in the last solve. Kissat does not report which
+    /// assumptions it needed, so this conservatively returns `true` for every
+    /// assumption of an unsatisfiable solve. The state of the solver must be
+    /// `Some(false)`.
+    pub fn failed(&self, lit: i32) -> bool {
+        self.failed.contains(&lit)
+    }
+
+    /// Returns the final conflict of the last solve if it was
+    /// unsatisfiable: a clause over the negated assumptions which is implied
+    /// by the formula. Kissat does not keep the conflict it derived, so this
+    /// is the negation of the failed assumptions (see `failed`), and thus the
+    /// empty clause if the solve had no assumptions.
+    pub fn final_conflict(&self) -> Option<Vec<i32>> {
+        if self.result != Some(Ok(false)) {
+            return None;
+        }
+        Some(self.failed.iter().map(|&lit| -lit).collect())
+    }
+
+    /// Reloads the recorded clauses into a fresh Kissat instance, so that
+    /// clauses can be added again after `solve`. The configuration is kept,
+    /// while learned clauses, statistics and the last solution are lost.
+    pub fn reset(&mut self) {
+        let ptr = unsafe { kissat_init() };
+        unsafe { kissat_release(std::mem::replace(&mut self.ptr, ptr)) };
+        self.solved = false;
+        self.result = None;
+        self.failed.clear();
+        self.load();
+    }
+
+    /// Returns the solver to the state of `Solver::new()`, with a fresh
+    /// Kissat instance, while keeping the allocated buffers.
+    fn clear(&mut self) {
+        self.cnf.clear();
+        self.max_var = 0;
+        self.config = None;
+        self.options.clear();
+        self.limits = Limits::default();
+        self.soft.clear();
+        self.dedup = Some(HashSet::new());
+        self.assumptions.clear();
+        self.assumed.clear();
+        *self.hooks = Hooks::default();
+        self.reset_counters();
+        self.reset();
+    }
+
+    /// Loads the configuration and recorded clauses into a fresh instance.
+    fn load(&mut self) {
+        if let Some(config) = &self.config {
+            unsafe { kissat_set_configuration(self.ptr, config.as_ptr()) };
+        }
+        for (name, value) in &self.options {
+            unsafe { kissat_set_option(self.ptr, name.as_ptr(), *value) };
+        }
+        if self.max_var > 0 {
+            unsafe { kissat_reserve(self.ptr, self.max_var) };
+        }
+        for clause in self.cnf.clauses() {
+            for &lit in clause {
+                unsafe { kissat_add(self.ptr, lit) };
+            }
+            unsafe { kissat_add(self.ptr, 0) };
+        }
+    }
+
+    /// Solves with an extra terminate callback, which is removed afterwards.
+    fn solve_scoped(&mut self, terminate: Terminate) -> Result<bool, Unknown> {
+        self.hooks.scoped.push(terminate);
+        let result = self.solve_result();
+        self.hooks.scoped.pop();
+        result
+    }
+
+    /// Sets a callback which Kissat polls regularly during `solve`. Once it
+    /// returns `true`, the solve is terminated and returns `None`.
+    /// The callback replaces any previously set one and stays in place
+    /// until `clear_terminate`.
+    pub fn set_terminate<F>(&mut self, terminate: F)
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        self.hooks.terminate = Some(Box::new(terminate));
+    }
+
+    /// Removes the callback set with `set_terminate`.
+    pub fn clear_terminate(&mut self) {
+        self.hooks.terminate = None;
+    }
+
+    /// Sets a callback invoked with the total number of restarts whenever
+    /// Kissat has restarted. Kissat has no restart hook, so the restart
+    /// counter is checked each time Kissat polls for termination, which it
+    /// does frequently during search: the callback runs at the first poll
+    /// after a restart, and if several restarts happen between two polls,
+    /// it is invoked once with the latest count. The check itself only
+    /// copies a few counters, so its cost is small next to the search.
+    /// The callback replaces any previously set one.
+    pub fn set_restart_callback<F>(&mut self, restart: F)
+    where
+        F: FnMut(u64) + Send + 'static,
+    {
+        self.hooks.restart = Some(Box::new(restart));
+    }
+
+    /// Sets a callback invoked with the current statistics and the time
+    /// since the start of the solve, at most once per `interval` while
+    /// solving, for example to print progress lines. It runs from Kissat's
+    /// termination polls, which only read the clock every few dozen polls,
+    /// so the search is barely slowed down; the first call comes after one
+    /// interval, and none is made if the solve finishes sooner. The
+    /// callback replaces any previously set one.
+    pub fn set_progress_logger<F>(&mut self, interval: Duration, logger: F)
+    where
+        F: FnMut(&Statistics, Duration) + Send + 'static,
+    {
+        let mut logger = logger;
+        let now = Instant::now();
+        self.hooks.progress = Some(Progress {
+            interval,
+            logger: Box::new(move |stats, elapsed, _| logger(stats, elapsed)),
+            trail: None,
+            start: now,
+            last: now,
+        });
+    }
+
+    /// Like `set_progress_logger`, but also passes the current trail: the
+    /// decisions and propagated literals of the search, in assignment order.
+    /// The trail is copied out of Kissat at every call, which costs time
+    /// linear in the number of assigned variables, so this is meant for
+    /// visualizing or debugging the search on small instances. Variables
+    /// Kissat introduced internally do not appear in the snapshot.
+    pub fn set_trail_logger<F>(&mut self, interval: Duration, logger: F)
+    where
+        F: FnMut(&Statistics, Duration, &[i32]) + Send + 'static,
+    {
+        let now = Instant::now();
+        self.hooks.progress = Some(Progress {
+            interval,
+            logger: Box::new(logger),
+            trail: Some(Vec::new()),
+            start: now,
+            last: now,
+        });
+    }
+
+    /// Makes the following solves stop and return `None` once the process
+    /// uses more than `bytes` of resident memory, reported as
+    /// `Unknown::Limit` by `solve_result`. Kissat has no memory limit of its
+    /// own, so the resident set size of the whole process, including any
+    /// other solvers and threads, is read from `/proc/self/status` every
+    /// few thousand termination polls. The limit is therefore approximate:
+    /// memory allocated between two checks, for example by a large
+    /// inprocessing step, can overshoot it, so leave some headroom below a
+    /// hard cgroup limit. Unlike `set_limit`, this applies to all
+    /// following solves. Only available on Linux.
+    #[cfg(target_os = "linux")]
+    pub fn set_memory_limit(&mut self, bytes: u64) {
+        self.hooks.memory_limit = Some(bytes);
+    }
+
+    /// Makes the following solves stop cleanly on Ctrl-C and return `None`,
+    /// instead of the process being killed in the middle of Kissat. The
+    /// SIGINT handler is only installed while `solve` runs, and the previous
+    /// handler is restored when it returns. Signal handlers are
+    /// process-wide, so this should not be used by solvers running
+    /// concurrently on several threads.
+    #[cfg(feature = "sigint")]
+    pub fn install_sigint_handler(&mut self) {
+        self.hooks.sigint = true;
+    }
+
+    /// Returns the value of the given literal in the last solution. The
+    /// state of the solver must be `Some(true)`. The returned value is
+    /// `None` if the formula is satisfied regardless of the value of the
+    /// literal.
+    #[inline]
+    pub fn value(&self, lit: i32) -> Option<bool> {
+        debug_assert!(valid_lit(lit));
+        let val = unsafe { kissat_value(self.ptr, lit) };
+        if val == lit {
+            Some(true)
+        } else if val == -lit {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value of the given literal in the last solution, like
+    /// `value`, but naming the don't-care case. The state of the solver must
+    /// be `Some(true)`.
+    pub fn value_detailed(&self, lit: i32) -> Assignment {
+        match self.value(lit) {
+            Some(true) => Assignment::True,
+            Some(false) => Assignment::False,
+            None => Assignment::DontCare,
+        }
+    }
+
+    /// Returns the value of the given variable in the last solution, with
+    /// the same meaning as `value` of its positive literal.
+    #[inline]
+    pub fn value_var(&self, var: u32) -> Option<bool> {
+        self.value(signed_var(var))
+    }
+
+    /// Returns the values of all variables in the last solution. The state of
+    /// the solver must be `Some(true)`.
+    pub fn model(&self) -> Model {
+        Model::from((1..=self.max_var).map(|var| self.value(var)).collect::<Vec<_>>())
+    }
+
+    /// Returns the values of all variables in the last solution as signs,
+    /// indexed by variable: index `v` holds 1 if `v` is true, -1 if it is
+    /// false and 0 if it is a don't-care. Index 0 is unused and holds 0, so
+    /// the vector has `num_vars() + 1` entries. The state of the solver must
+    /// be `Some(true)`.
+    pub fn model_signs(&self) -> Vec<i8> {
+        let values = (1..=self.max_var).map(|var| match self.value(var) {
+            Some(true) => 1,
+            Some(false) => -1,
+            None => 0,
+        });
+        std::iter::once(0).chain(values).collect()
+    }
+
+    /// Returns the number of variables in `1..=num_vars()` which the last
+    /// solution leaves unassigned, those for which `value` returns `None`.
+    /// The state of the solver must be `Some(true)`.
+    pub fn dont_care_count(&self) -> i32 {
+        (1..=self.max_var).filter(|&var| self.value(var).is_none()).count() as i32
+    }
+
+    /// Returns a locally minimal model: no variable which is true in it can
+    /// be made false without making some other false variable true. Starting
+    /// from the last solution, it repeatedly solves the formula with every
+    /// false variable fixed and a clause requiring one true variable to flip,
+    /// until that is unsatisfiable. As Kissat is not incremental, each of
+    /// these solves reloads `cnf()` into a fresh instance, leaving this
+    /// solver untouched. Don't-care variables are set to false. The state of
+    /// the solver must be `Some(true)`.
+    pub fn minimize_model(&self) -> Model {
+        let mut values: Vec<bool> = (1..=self.max_var).map(|var| self.value(var) == Some(true)).collect();
+        loop {
+            let mut sat = self.reload();
+            let mut flip = Vec::new();
+            for (var, &val) in (1..).zip(values.iter()) {
+                if val {
+                    flip.push(-var);
+                } else {
+                    sat.add_clause([-var]);
+                }
+            }
+            if flip.is_empty() {
+                break;
+            }
+            sat.add_clause(flip);
+            if sat.solve() != Some(true) {
+                break;
+            }
+            for (var, val) in (1..).zip(values.iter_mut()) {
+                *val = sat.value(var) == Some(true);
+            }
+        }
+        Model::from(values.into_iter().map(Some).collect::<Vec<_>>())
+    }
+
+    /// Estimates how hard the formula is, by solving it on a fresh instance
+    /// with the configuration, options and clauses of this solver, but
+    /// without assumptions, for at most `conflict_budget` conflicts. This
+    /// solver is left untouched, so it can still be solved in full.
+    pub fn probe_hardness(&self, conflict_budget: u32) -> HardnessEstimate {
+        let mut sat = self.reload();
+        sat.limits.conflicts = Some(conflict_budget);
+        let start = Instant::now();
+        let result = sat.solve();
+        let elapsed = start.elapsed();
+        HardnessEstimate {
+            result,
+            conflicts: sat.statistics().conflicts,
+            elapsed,
+        }
+    }
+
+    /// Counts the models of the formula over the variables `1..=num_vars()`,
+    /// stopping once `limit` models are found or the count is complete.
+    /// Each model is found by a fresh solve of `cnf()` with all previous
+    /// models blocked, so this performs up to `limit + 1` solves of a
+    /// growing formula; it answers "are there more than K models?" for
+    /// small K, it is not a model counter. This solver is left untouched.
+    pub fn count_models(&self, limit: usize) -> usize {
+        let mut blocked = Cnf::new();
+        while blocked.len() < limit {
+            let mut sat = self.reload();
+            for clause in blocked.clauses() {
+                sat.add_clause(clause.iter().copied());
+            }
+            if sat.solve() != Some(true) {
+                break;
+            }
+            blocked.add_clause((1..=self.max_var).map(|var| if sat.value(var) == Some(true) { -var } else { var }));
+        }
+        blocked.len()
+    }
+
+    /// Returns the indices, into `cnf().clauses()`, of a minimal subset of
+    /// the clauses which is unsatisfiable on its own. Kissat is compiled
+    /// without proof support, so the core cannot be read off a proof;
+    /// instead each clause is dropped in turn and kept out if the rest stays
+    /// unsatisfiable. This costs one solve per recorded clause, on fresh
+    /// instances, leaving this solver untouched. Returns an empty vector if
+    /// the formula is not unsatisfiable.
+    pub fn unsat_clause_core(&self) -> Vec<usize> {
+        let clauses: Vec<&[i32]> = self.cnf.clauses().collect();
+        mus::deletion_mus(&clauses, || self.scratch()).unwrap_or_default()
+    }
+
+    /// Returns a fresh solver loaded with the configuration and recorded
+    /// clauses.
+    fn reload(&self) -> Solver {
+        let mut sat = Solver::new();
+        sat.cnf = self.cnf.clone();
+        sat.max_var = self.max_var;
+        sat.config = self.config.clone();
+        sat.options = self.options.clone();
+        sat.load();
+        sat
+    }
+
+    /// Returns a fresh solver with the same configuration and variables,
+    /// but no clauses.
+    fn scratch(&self) -> Solver {
+        let mut sat = Solver::new();
+        sat.max_var = self.max_var;
+        sat.config = self.config.clone();
+        sat.options = self.options.clone();
+        sat.load();
+        sat
+    }
+
+    /// Returns the values of the given literals in the last solution, with
+    /// the same meaning as `value`. Kissat has no batched accessor, but as it
+    /// is linked statically each lookup is a plain function call.
+    pub fn values(&self, lits: &[i32]) -> Vec<Option<bool>> {
+        lits.iter().map(|&lit| self.value(lit)).collect()
+    }
+
+    /// Returns whether the solver proved that the literals `a` and `b` are
+    /// equivalent. Kissat does substitute equivalent literals during
+    /// inprocessing, but it does not expose them through its API, so this
+    /// is conservative: only the trivial equivalence `a == b` is reported,
+    /// and `false` means "not known", never "not equivalent".
+    #[inline]
+    pub fn equivalent(&self, a: i32, b: i32) -> bool {
+        debug_assert!(valid_lit(a));
+        debug_assert!(valid_lit(b));
+        a == b
+    }
+
+    /// Sets a solver limit with the corresponding name to the given value.
+    /// These limits are only valid for the next `solve` call
+    /// and reset to their default values, which disables them.
+    /// The following limits are supported:
+    /// * `conflicts`: max conflicts detected before the solver aborts.
+    /// * `decisions`: max decisions made before the solver aborts.
+    pub fn set_limit<S: AsRef<str>>(&mut self, name: S, limit: u32) -> Result<(), Error> {
+        match name.as_ref() {
+            "conflicts" => self.limits.conflicts = Some(limit),
+            "decisions" => self.limits.decisions = Some(limit),
+            _ => return Err(Error::new("unknown limit")),
+        };
+        Ok(())
+    }
+
+    /// Sets all the limits given in `limits` for the next `solve` call,
+    /// as `set_limit` does.
+    pub fn set_limits(&mut self, limits: &Limits) {
+        if limits.conflicts.is_some() {
+            self.limits.conflicts = limits.conflicts;
+        }
+        if limits.decisions.is_some() {
+            self.limits.decisions = limits.decisions;
+        }
+    }
+
+    /// Returns the number of solves run by this solver, including those run
+    /// by queries such as `entails`, since it was created or since
+    /// `reset_counters`. Unlike Kissat's statistics, this survives `reset`.
+    pub fn solve_count(&self) -> u64 {
+        self.solves
+    }
+
+    /// Returns the total time spent in Kissat's solve function, counted
+    /// like `solve_count`.
+    pub fn total_solve_time(&self) -> Duration {
+        self.solve_time
+    }
+
+    /// Resets `solve_count` and `total_solve_time` to zero.
+    pub fn reset_counters(&mut self) {
+        self.solves = 0;
+        self.solve_time = Duration::ZERO;
+    }
+
+    /// Returns the current values of Kissat's search counters.
+    pub fn statistics(&self) -> Statistics {
+        let mut stats = Statistics::default();
+        unsafe { cat_solver_statistics(self.ptr, &mut stats) };
+        stats
+    }
+
+    /// Returns the number of irredundant clauses in Kissat's database: the
+    /// original clauses which survived simplification, along with all binary
+    /// clauses, including learned ones, as counted by Kissat's statistics.
+    /// Kissat can compile its statistics out with `NSTATISTICS`, but
+    /// build.rs never defines it, so the counts are always available. Most
+    /// useful after a solve, to see how the database evolved.
+    pub fn irredundant_clauses(&self) -> u64 {
+        self.clause_counts().0
+    }
+
+    /// Returns the number of redundant (learned) clauses longer than two
+    /// in Kissat's database, as counted by its statistics; learned binary
+    /// clauses are counted by `irredundant_clauses` instead. Reductions
+    /// remove most of them over time.
+    pub fn redundant_clauses(&self) -> u64 {
+        self.clause_counts().1
+    }
+
+    fn clause_counts(&self) -> (u64, u64) {
+        let (mut irredundant, mut redundant) = (0, 0);
+        unsafe { cat_solver_clauses(self.ptr, &mut irredundant, &mut redundant) };
+        (irredundant, redundant)
+    }
+
+    /// Returns the current statistics as a JSON object, such as
+    /// `{"conflicts":12,"decisions":30,"propagations":95,"restarts":0}`.
+    /// Counters may be added in later releases, but the existing field
+    /// names are not renamed or removed.
+    #[cfg(feature = "serde")]
+    pub fn statistics_json(&self) -> String {
+        serde_json::to_string(&self.statistics()).expect("statistics serialize to JSON")
+    }
+
+    /// Releases the solver and returns its final statistics.
+    pub fn finish(self) -> Statistics {
+        self.statistics()
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::new()
+    }
+}
+
+/// Constructs a solver loaded with the clauses of the formula.
+impl From<&Cnf> for Solver {
+    fn from(cnf: &Cnf) -> Self {
+        let mut sat = Solver::new();
+        sat.cnf = cnf.clone();
+        sat.max_var = cnf.num_vars();
+        sat.load();
+        sat
+    }
+}
+
+/// Constructs a solver loaded with the clauses of the snapshot.
+impl From<&CnfSnapshot> for Solver {
+    fn from(snapshot: &CnfSnapshot) -> Self {
+        let mut sat = Solver::new();
+        sat.cnf = snapshot.to_cnf();
+        sat.max_var = sat.cnf.num_vars();
+        sat.load();
+        sat
+    }
+}
+
+impl Drop for Solver {
+    fn drop(&mut self) {
+        unsafe { kissat_release(self.ptr) };
+    }
+}
+
+/// Kissat does not use thread local variables, so it is possible to
+/// move it between threads. However it cannot be used queried concurrently
+/// (for example getting the value from multiple threads at once), so we
+/// do not implement `Sync`.
+unsafe impl Send for Solver {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Error type for configuration errors.
+pub struct Error {
+    pub msg: String,
+}
+
+impl Error {
+    pub fn new(msg: &str) -> Self {
+        Error {
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.msg.fmt(f)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Limits for a single solve. `None` leaves the limit disabled.
+pub struct Limits {
+    /// Max conflicts detected before the solver aborts.
+    pub conflicts: Option<u32>,
+    /// Max decisions made before the solver aborts.
+    pub decisions: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The value of a literal in a solution, as returned by `value_detailed`.
+pub enum Assignment {
+    /// The literal is true.
+    True,
+    /// The literal is false.
+    False,
+    /// The solution satisfies the formula whatever the literal's value.
+    DontCare,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The outcome of a solve, together with the model if there is one.
+pub enum Outcome {
+    /// The formula is satisfiable, with the given model.
+    Sat(Model),
+    /// The formula is unsatisfiable.
+    Unsat,
+    /// The solve ended without a decision.
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The result of `solve_under`.
+pub enum SolveResult {
+    /// The formula is satisfiable under the assumptions.
+    Sat,
+    /// The formula is unsatisfiable under the assumptions.
+    Unsat,
+    /// The solve ended without a decision, for the given reason.
+    Unknown(Unknown),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The initial decision phase, for `set_initial_phase`.
+pub enum InitialPhase {
+    /// Decide variables as true first.
+    True,
+    /// Decide variables as false first.
+    False,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// A rough measure of an instance's difficulty, from `probe_hardness`.
+pub struct HardnessEstimate {
+    /// The result of the bounded solve: `None` if the budget ran out.
+    pub result: Option<bool>,
+    /// The number of conflicts of the bounded solve.
+    pub conflicts: u64,
+    /// The wall-clock time of the bounded solve.
+    pub elapsed: Duration,
+}
+
+impl HardnessEstimate {
+    /// Returns whether the bounded solve decided the formula.
+    pub fn decided(&self) -> bool {
+        self.result.is_some()
+    }
+
+    /// Returns the conflict rate of the bounded solve. A low rate suggests
+    /// expensive propagation, for example on large instances.
+    pub fn conflicts_per_second(&self) -> f64 {
+        self.conflicts as f64 / self.elapsed.as_secs_f64().max(1e-9)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The result of `solve_with_core`.
+pub enum CoreResult {
+    /// The formula is satisfiable under the assumptions, with the given model.
+    Sat(Model),
+    /// The formula is unsatisfiable under the assumptions, with the given
+    /// failed assumptions.
+    Unsat(Vec<i32>),
+    /// The solve ended without a decision.
+    Unknown,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+/// Search statistics, as counted by Kissat. With the `serde` feature they
+/// serialize under the field names, which are kept stable across releases.
+pub struct Statistics {
+    /// Number of conflicts encountered.
+    pub conflicts: u64,
+    /// Number of decisions made.
+    pub decisions: u64,
+    /// Number of propagated literals.
+    pub propagations: u64,
+    /// Number of restarts.
+    pub restarts: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The reason a solve ended without deciding the formula.
+pub enum Unknown {
+    /// A limit set with `set_limit` was reached.
+    Limit,
+    /// The solver was terminated before reaching a decision.
+    Terminated,
+}
+
+impl fmt::Display for Unknown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Unknown::Limit => "limit reached".fmt(f),
+            Unknown::Terminated => "terminated".fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
     use std::thread;
 
+    #[test]
+    fn literals() {
+        assert_eq!(lit(1, true), 1);
+        assert_eq!(lit(1, false), -1);
+        assert_eq!(lit(i32::MAX, false), -i32::MAX);
+        assert_eq!(var_of(-i32::MAX), i32::MAX);
+        assert_eq!(var_of(7), 7);
+        assert_eq!(negate(i32::MAX), -i32::MAX);
+        assert_eq!(negate(-1), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn negate_min() {
+        negate(i32::MIN);
+    }
+
+    #[test]
+    #[should_panic]
+    fn var_of_zero() {
+        var_of(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn lit_zero() {
+        lit(0, true);
+    }
+
+    #[test]
+    fn from_raw() {
+        let ptr = unsafe { kissat_init() };
+        for lit in [1, 2, 0, -1, 0] {
+            unsafe { kissat_add(ptr, lit) };
+        }
+        let mut sat = unsafe { Solver::from_raw(ptr) };
+        sat.reserve(2);
+        assert!(sat.cnf().is_empty());
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.model().value(2), Some(true));
+    }
+
     #[test]
     fn solver() {
         let mut sat: Solver = Solver::new();
@@ -185,7 +1804,19 @@ mod tests {
         assert_eq!(sat.solve(), Some(false));
     }
 
-    fn pigeon_hole(num: i32) -> Solver {
+    #[test]
+    fn version() {
+        assert!(Solver::new().signature().contains(KISSAT_VERSION));
+        assert!(!kissat_gitid().is_empty());
+    }
+
+    #[test]
+    fn variable_ceiling() {
+        assert!(is_compact_build());
+        assert_eq!(max_supported_vars(), (1 << 28) - 1);
+    }
+
+    pub(crate) fn pigeon_hole(num: i32) -> Solver {
         let mut sat: Solver = Solver::new();
         for i in 0..(num + 1) {
             sat.add_clause((0..num).map(|j| 1 + i * num + j));
@@ -205,6 +1836,256 @@ mod tests {
         sat
     }
 
+    #[test]
+    fn add_clause_strict() {
+        let mut sat = Solver::new();
+        assert!(!sat.add_clause_strict([1, 2, -1]));
+        assert!(sat.add_clause_strict([-2, -2]));
+        assert!(sat.add_clause_strict([1, 2, 1]));
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.value(1), Some(true));
+        assert_eq!(sat.value(2), Some(false));
+    }
+
+    #[test]
+    fn lit_clause() {
+        let mut sat = Solver::new();
+        sat.add_clause([Lit::new(1, true), Lit::new(2, true)]);
+        sat.add_clause(vec![-Lit::new(1, true)]);
+        sat.add_clause([-2, 3]);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.value(3), Some(true));
+    }
+
+    #[test]
+    fn var_clause() {
+        let mut sat = Solver::new();
+        sat.add_var_clause([(1, true), (2, true)]);
+        sat.add_var_clause([(1, false)]);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.value_var(1), Some(false));
+        assert_eq!(sat.value_var(2), Some(true));
+    }
+
+    #[test]
+    fn with_config() {
+        let mut sat = Solver::with_config(2, "sat").unwrap();
+        sat.add_clause([1, 2]);
+        sat.add_clause([-1]);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.value(2), Some(true));
+        assert!(Solver::with_config(2, "bad").is_err());
+        assert_eq!(Solver::with_config(0, "plain").unwrap().num_vars(), 0);
+    }
+
+    #[test]
+    fn set_option() {
+        let mut sat = Solver::new();
+        assert!(sat.set_target(1).is_ok());
+        assert!(sat.set_target(3).is_err());
+        assert!(sat.set_reduce_fraction(75).is_ok());
+        assert!(sat.set_reduce_fraction(5).is_err());
+        assert_eq!(sat.set_option("nosuchoption", 1), Err(Error::new("cannot set option 'nosuchoption' to 1")));
+        #[cfg(feature = "options")]
+        assert_eq!(sat.set_option("nosuchoption", 0), Err(Error::new("cannot set option 'nosuchoption' to 0")));
+        assert!(sat.set_option("seed", 0).is_ok());
+        sat.add_clause([1, 2]);
+        assert_eq!(sat.solve(), Some(true));
+    }
+
+    #[test]
+    fn set_options() {
+        let mut sat = Solver::new();
+        assert_eq!(sat.set_options(&[("target", 1), ("target", 7)]), Err(Error::new("cannot set option 'target' to 7")));
+        assert!(sat.options.is_empty());
+        assert!(sat.set_options(&[("target", 1)]).is_ok());
+        assert_eq!(sat.options.len(), 1);
+    }
+
+    #[test]
+    fn glue_threshold() {
+        let mut sat = pigeon_hole(4);
+        assert!(sat.set_glue_threshold(6).is_ok());
+        assert!(sat.set_glue_threshold(0).is_err());
+        assert_eq!(sat.set_glue_threshold(u32::MAX), Err(Error::new("glue threshold out of range")));
+        #[cfg(feature = "options")]
+        sat.set_glue_threshold(3).unwrap();
+        assert_eq!(sat.solve(), Some(false));
+    }
+
+    #[test]
+    fn vivify() {
+        let mut sat = pigeon_hole(4);
+        assert!(sat.set_vivify(true).is_ok());
+        assert!(sat.set_vivify_effort(100).is_ok());
+        assert_eq!(sat.set_vivify_effort(u32::MAX), Err(Error::new("vivify effort out of range")));
+        #[cfg(feature = "options")]
+        {
+            sat.set_vivify(false).unwrap();
+            sat.set_vivify_effort(10).unwrap();
+        }
+        assert_eq!(sat.solve(), Some(false));
+    }
+
+    #[test]
+    fn initial_phase() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        assert!(sat.set_initial_phase(InitialPhase::True).is_ok());
+        assert_eq!(sat.solve(), Some(true));
+        #[cfg(not(feature = "options"))]
+        assert!(sat.set_initial_phase(InitialPhase::False).is_err());
+        #[cfg(feature = "options")]
+        {
+            sat.set_initial_phase(InitialPhase::False).unwrap();
+            sat.set_option("forcephase", 1).unwrap();
+            assert_eq!(sat.solve(), Some(true));
+            let model = sat.model();
+            assert_ne!(model.value(1), model.value(2));
+        }
+    }
+
+    #[test]
+    fn reseed() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        assert!(sat.reseed(0).is_ok());
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.reseed(u32::MAX), Err(Error::new("seed out of range")));
+        #[cfg(feature = "options")]
+        for seed in 1..4 {
+            sat.reseed(seed).unwrap();
+            assert_eq!(sat.solve(), Some(true));
+        }
+    }
+
+    #[cfg(feature = "options")]
+    #[test]
+    fn set_option_replayed() {
+        let mut sat = Solver::new();
+        sat.set_target(2).unwrap();
+        sat.add_clause([1, 2]);
+        assert_eq!(sat.solve(), Some(true));
+        sat.reset();
+        assert_eq!(unsafe { kissat_get_option(sat.ptr, CString::new("target").unwrap().as_ptr()) }, 2);
+    }
+
+    #[test]
+    fn is_satisfiable() {
+        let mut cnf = Cnf::new();
+        cnf.add_clause([1, 2]);
+        cnf.add_clause([-1]);
+        assert_eq!(super::is_satisfiable(&cnf), Some(true));
+        cnf.add_clause([-2]);
+        assert_eq!(super::is_satisfiable(&cnf), Some(false));
+    }
+
+    #[test]
+    fn soft_clauses() {
+        let mut sat = Solver::new();
+        sat.add_clause([1]);
+        sat.add_clause([2]);
+        let r1 = sat.add_soft_clause(&[-1], 3);
+        let r2 = sat.add_soft_clause(&[-1, -2], 1);
+        assert_eq!((r1, r2), (3, 4));
+        assert_eq!(sat.soft_clauses(), [(r1, 3), (r2, 1)]);
+        sat.assume(-r1);
+        assert_eq!(sat.solve(), Some(false));
+        sat.assume(-r2);
+        assert_eq!(sat.solve(), Some(false));
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!((sat.value(r1), sat.value(r2)), (Some(true), Some(true)));
+        let mut sat = Solver::new();
+        assert_eq!(sat.add_soft_clause(&[1], 3), 2);
+        assert_eq!(sat.cnf().clauses().next(), Some(&[1, 2][..]));
+    }
+
+    #[test]
+    fn can_add_clauses() {
+        let mut sat = Solver::new();
+        assert!(sat.can_add_clauses());
+        sat.add_clause([1]);
+        assert_eq!(sat.solve(), Some(true));
+        assert!(!sat.can_add_clauses());
+        sat.reset();
+        assert!(sat.can_add_clauses());
+    }
+
+    #[test]
+    fn add_clauses() {
+        let mut sat = Solver::new();
+        sat.add_clauses(vec![vec![1, 2], vec![-1], vec![-2, 3]]);
+        assert_eq!(sat.num_vars(), 3);
+        assert_eq!(sat.cnf().len(), 3);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.value(3), Some(true));
+    }
+
+    #[test]
+    fn value_detailed() {
+        let mut sat = Solver::new();
+        sat.add_clause([1]);
+        sat.add_clause([-2]);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.value_detailed(1), Assignment::True);
+        assert_eq!(sat.value_detailed(2), Assignment::False);
+        assert_eq!(sat.value_detailed(-2), Assignment::True);
+        assert_eq!(sat.value_detailed(3), Assignment::DontCare);
+    }
+
+    #[test]
+    fn model_signs() {
+        let mut sat = Solver::new();
+        sat.add_clause([1]);
+        sat.add_clause([-2]);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.model_signs(), [0, 1, -1]);
+    }
+
+    #[test]
+    fn with_capacity() {
+        let mut sat = Solver::with_capacity(3, 2);
+        assert_eq!(sat.num_vars(), 3);
+        sat.add_clause([1, -2]);
+        sat.add_clause([2]);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.value(1), Some(true));
+    }
+
+    #[test]
+    fn new_var() {
+        let mut sat = Solver::new();
+        assert_eq!(sat.new_var(), 1);
+        sat.add_clause([-1, 5]);
+        assert_eq!(sat.new_var(), 6);
+        assert_eq!(sat.num_vars(), 6);
+    }
+
+    #[test]
+    fn set_config() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.set_config("unsat").unwrap();
+        assert_eq!(sat.set_config("bad"), Err(Error::new("unknown configuration")));
+        sat.add_clause([-1]);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.value(2), Some(true));
+        assert_eq!(sat.cnf().len(), 2);
+    }
+
+    #[test]
+    fn reserve() {
+        let mut sat = Solver::new();
+        sat.reserve(5);
+        sat.reserve(3);
+        assert_eq!(sat.num_vars(), 5);
+        sat.add_clause([-7]);
+        sat.reserve(6);
+        assert_eq!(sat.num_vars(), 7);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.model().num_vars(), 7);
+    }
+
     #[test]
     fn decision_limit() {
         let mut sat = pigeon_hole(5);
@@ -221,12 +2102,435 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn solve_result() {
+        let mut sat = pigeon_hole(5);
+        sat.set_limit("conflicts", 100).unwrap();
+        assert_eq!(sat.solve_result(), Err(Unknown::Limit));
+        let mut sat = pigeon_hole(3);
+        assert_eq!(sat.solve_result(), Ok(false));
+        let mut sat = pigeon_hole(9);
+        sat.set_terminate(|| true);
+        sat.set_limit("conflicts", 1_000_000).unwrap();
+        assert_eq!(sat.solve_result(), Err(Unknown::Terminated));
+        sat.clear_terminate();
+        sat.set_limit("conflicts", 100).unwrap();
+        assert_eq!(sat.solve_result(), Err(Unknown::Limit));
+    }
+
+    #[test]
+    fn statistics() {
+        let mut sat = pigeon_hole(5);
+        assert_eq!(sat.statistics(), Statistics::default());
+        sat.set_limit("conflicts", 100).unwrap();
+        assert_eq!(sat.solve(), None);
+        let stats = sat.finish();
+        assert!(stats.conflicts > 0);
+        assert!(stats.decisions > 0);
+    }
+
+    #[test]
+    fn terminate() {
+        let mut sat = pigeon_hole(10);
+        let mut polls = 0;
+        sat.set_terminate(move || {
+            polls += 1;
+            polls > 10
+        });
+        assert_eq!(sat.solve_result(), Err(Unknown::Terminated));
+    }
+
+    #[test]
+    fn restart_callback() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+        let mut sat = pigeon_hole(7);
+        let last = Arc::new(AtomicU64::new(0));
+        let seen = last.clone();
+        sat.set_restart_callback(move |restarts| {
+            assert!(restarts > seen.load(Ordering::Relaxed));
+            seen.store(restarts, Ordering::Relaxed);
+        });
+        sat.set_limit("conflicts", 5000).unwrap();
+        sat.solve();
+        let last = last.load(Ordering::Relaxed);
+        assert!(last > 0 && last <= sat.statistics().restarts);
+    }
+
+    #[test]
+    fn set_limits() {
+        let mut sat = pigeon_hole(5);
+        sat.set_limits(&Limits {
+            conflicts: Some(100),
+            ..Default::default()
+        });
+        assert_eq!(sat.solve_result(), Err(Unknown::Limit));
+    }
+
+    #[test]
+    fn entails() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.add_clause([-1, 2]);
+        assert_eq!(sat.entails(2), Some(true));
+        assert_eq!(sat.entails(1), Some(false));
+        assert_eq!(sat.value(1), Some(false));
+        assert_eq!(sat.entails(-1), Some(false));
+    }
+
+    #[test]
+    fn backbone() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.add_clause([-1, 2]);
+        sat.add_clause([-3, 4]);
+        sat.add_clause([3, -5]);
+        sat.add_clause([-2, -5]);
+        assert_eq!(sat.backbone(), [2, -5]);
+        sat.assume(3);
+        assert_eq!(sat.backbone(), [2, 3, 4, -5]);
+        sat.reset();
+        sat.add_clause([-2]);
+        assert!(sat.backbone().is_empty());
+    }
+
+    #[test]
+    fn minimize_clause() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.add_clause([-2, 3]);
+        assert_eq!(sat.minimize_clause(&[4, 1, 3, -5]), [1, 3]);
+        assert_eq!(sat.minimize_clause(&[1, 4]), [1, 4]);
+        sat.assume(-1);
+        assert_eq!(sat.minimize_clause(&[2, 3]), [3]);
+    }
+
+    #[test]
+    fn minimal_correction_set() {
+        let mut sat = Solver::new();
+        sat.add_clause([-1, -2]);
+        sat.add_clause([-2, -3]);
+        assert_eq!(sat.minimal_correction_set(&[1, 2, 3, 4]), [2]);
+        assert_eq!(sat.minimal_correction_set(&[2, 1, 3]), [1, 3]);
+        assert!(sat.minimal_correction_set(&[1, 3]).is_empty());
+        sat.assume(3);
+        assert_eq!(sat.minimal_correction_set(&[2, 1]), [2]);
+    }
+
+    #[test]
+    fn solve_self_checked() {
+        let mut sat = pigeon_hole(4);
+        assert_eq!(sat.solve_self_checked(), Some(false));
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.assume(-1);
+        assert_eq!(sat.solve_self_checked(), Some(true));
+        assert_eq!(sat.value(2), Some(true));
+    }
+
+    #[test]
+    fn solve_assuming() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.assume(1);
+        assert_eq!(sat.solve_assuming(&[-1, -2]), Some(false));
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.solve_assuming(&[-1]), Some(true));
+        assert_eq!(sat.value(2), Some(true));
+        assert_eq!(sat.solve_assuming(&[-2]), Some(true));
+        assert_eq!(sat.value(1), Some(true));
+        let mut sat = pigeon_hole(7);
+        sat.set_limit("conflicts", 10).unwrap();
+        assert_eq!(sat.solve_assuming(&[1]), None);
+        assert!(sat.assumptions.is_empty());
+    }
+
+    #[test]
+    fn solve_under() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        assert_eq!(sat.solve_under([-1]), SolveResult::Sat);
+        sat.assume(-1);
+        assert_eq!(sat.solve_under([-2]), SolveResult::Unsat);
+        assert!(sat.failed(-1) && sat.failed(-2));
+        #[cfg(feature = "fixedbitset")]
+        assert_eq!(sat.core_bitset().map(|core| core.ones().collect::<Vec<_>>()), Some(vec![0, 1]));
+        let mut sat = pigeon_hole(6);
+        sat.set_limit("conflicts", 10).unwrap();
+        assert_eq!(sat.solve_under([]), SolveResult::Unknown(Unknown::Limit));
+        #[cfg(feature = "fixedbitset")]
+        assert_eq!(sat.core_bitset(), None);
+    }
+
+    #[test]
+    fn solve_counters() {
+        let mut sat = Solver::new();
+        sat.add_clause([1]);
+        assert_eq!(sat.solve_count(), 0);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.entails(1), Some(true));
+        assert_eq!(sat.solve_count(), 2);
+        let time = sat.total_solve_time();
+        assert_eq!(sat.solve(), Some(true));
+        assert!(sat.total_solve_time() >= time);
+        sat.reset_counters();
+        assert_eq!(sat.solve_count(), 0);
+        assert_eq!(sat.total_solve_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn solve_with_core() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        match sat.solve_with_core([-1]) {
+            CoreResult::Sat(model) => assert_eq!(model.value(2), Some(true)),
+            result => panic!("unexpected {:?}", result),
+        }
+        assert_eq!(sat.solve_with_core([-1, -2]), CoreResult::Unsat(vec![-1, -2]));
+        let mut sat = pigeon_hole(6);
+        sat.set_limit("conflicts", 10).unwrap();
+        assert_eq!(sat.solve_with_core([]), CoreResult::Unknown);
+    }
+
+    #[test]
+    fn add_clause_dedup() {
+        let mut sat = Solver::new();
+        assert!(sat.add_clause_dedup(&[2, -1]));
+        assert!(!sat.add_clause_dedup(&[-1, 2, 2]));
+        assert!(sat.add_clause_dedup(&[-1]));
+        assert_eq!(sat.cnf().clauses().collect::<Vec<_>>(), [&[-1, 2][..], &[-1][..]]);
+        sat.set_clause_dedup(false);
+        assert!(sat.add_clause_dedup(&[-1]));
+        sat.set_clause_dedup(true);
+        assert!(sat.add_clause_dedup(&[-1]));
+        assert!(!sat.add_clause_dedup(&[-1]));
+        assert_eq!(sat.cnf().len(), 4);
+    }
+
+    #[test]
+    fn add_clause_tracked() {
+        let mut sat = Solver::new();
+        assert_eq!(sat.add_clause_tracked([1, -3]), Some(3));
+        assert_eq!(sat.add_clause_tracked([2, -1]), None);
+        assert_eq!(sat.add_clause_tracked(Vec::<i32>::new()), None);
+        sat.reserve(5);
+        assert_eq!(sat.add_clause_tracked([5]), None);
+        assert_eq!(sat.add_clause_tracked([-6]), Some(6));
+    }
+
+    #[test]
+    fn try_add_clause() {
+        let mut sat = Solver::new();
+        let max_var = max_supported_vars() as i32;
+        assert!(sat.try_reserve(max_var + 1).is_err());
+        assert_eq!(sat.num_vars(), 0);
+        assert!(sat.try_add_clause([1, 0]).is_err());
+        assert!(sat.try_add_clause([-max_var - 1]).is_err());
+        assert!(sat.cnf().is_empty());
+        sat.try_add_clause([1, -2]).unwrap();
+        assert_eq!(sat.solve(), Some(true));
+        assert!(sat.try_add_clause([2]).is_err());
+        assert_eq!(sat.cnf().len(), 1);
+    }
+
+    #[test]
+    fn probe_hardness() {
+        let mut sat = pigeon_hole(7);
+        let estimate = sat.probe_hardness(100);
+        assert!(!estimate.decided());
+        assert!(estimate.conflicts >= 100);
+        assert!(estimate.conflicts_per_second() > 0.0);
+        assert!(sat.probe_hardness(u32::MAX).decided());
+        assert!(sat.can_add_clauses());
+        assert_eq!(pigeon_hole(2).probe_hardness(1000).result, Some(false));
+        sat.set_limit("conflicts", 10).unwrap();
+        assert_eq!(sat.solve(), None);
+    }
+
+    #[test]
+    fn feasible_units() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.add_clause([-1, 3]);
+        sat.add_clause([-2, -3]);
+        assert_eq!(sat.feasible_units(&[1, -1, 2, -2, 3, -3]), [1, -1, 2, -2, 3, -3]);
+        sat.assume(-3);
+        assert_eq!(sat.feasible_units(&[1, -1, 2, -2, 3, -3]), [-1, 2, -3]);
+        assert!(sat.feasible_units(&[]).is_empty());
+        assert_eq!(pigeon_hole(2).feasible_units(&[1, -1]), []);
+    }
+
+    #[test]
+    fn continue_solve() {
+        let mut sat = pigeon_hole(5);
+        sat.set_limit("conflicts", 10).unwrap();
+        assert_eq!(sat.solve(), None);
+        assert_eq!(sat.continue_solve(u32::MAX), Some(false));
+        assert_eq!(sat.continue_solve(0), Some(false));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn memory_limit() {
+        assert!(resident_set_size().is_some_and(|rss| rss > 0));
+        let mut sat = pigeon_hole(9);
+        sat.set_memory_limit(1);
+        assert_eq!(sat.solve_result(), Err(Unknown::Limit));
+        let mut sat = pigeon_hole(2);
+        sat.set_memory_limit(u64::MAX);
+        assert_eq!(sat.solve(), Some(false));
+    }
+
+    #[test]
+    fn progress_logger() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+        let conflicts = Arc::new(AtomicU64::new(0));
+        let counter = conflicts.clone();
+        let mut sat = pigeon_hole(9);
+        sat.set_progress_logger(Duration::ZERO, move |stats, _| {
+            counter.fetch_max(stats.conflicts, Ordering::Relaxed);
+        });
+        sat.set_limit("conflicts", 1000).unwrap();
+        assert_eq!(sat.solve(), None);
+        assert!((1..=1000).contains(&conflicts.load(Ordering::Relaxed)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn statistics_json() {
+        let sat = Solver::new();
+        assert_eq!(sat.statistics_json(), r#"{"conflicts":0,"decisions":0,"propagations":0,"restarts":0}"#);
+    }
+
+    #[test]
+    fn clause_counts() {
+        let mut sat = pigeon_hole(7);
+        sat.set_limit("conflicts", 1000).unwrap();
+        assert_eq!(sat.solve(), None);
+        assert!(sat.irredundant_clauses() > 0);
+        assert!(sat.redundant_clauses() > 0);
+    }
+
+    #[test]
+    fn trail_logger() {
+        use std::sync::{Arc, Mutex};
+        let trails = Arc::new(Mutex::new(Vec::new()));
+        let samples = trails.clone();
+        let mut sat = pigeon_hole(9);
+        sat.set_trail_logger(Duration::ZERO, move |_, _, trail| {
+            samples.lock().unwrap().push(trail.to_vec());
+        });
+        sat.set_limit("conflicts", 1000).unwrap();
+        assert_eq!(sat.solve(), None);
+        let trails = trails.lock().unwrap();
+        assert!(!trails.is_empty());
+        for trail in trails.iter() {
+            let mut vars: Vec<i32> = trail.iter().map(|lit| lit.abs()).collect();
+            assert!(vars.iter().all(|&var| (1..=90).contains(&var)));
+            vars.sort_unstable();
+            vars.dedup();
+            assert_eq!(vars.len(), trail.len());
+        }
+    }
+
+    #[test]
+    fn assumptions() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.assume(-1);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.value(2), Some(true));
+        sat.assume(-1);
+        sat.assume(-2);
+        assert_eq!(sat.solve(), Some(false));
+        assert!(sat.failed(-1) && sat.failed(-2));
+        assert!(!sat.failed(1));
+        assert_eq!(sat.final_conflict(), Some(vec![1, 2]));
+        assert_eq!(sat.solve(), Some(true));
+        assert!(!sat.failed(-1));
+        assert_eq!(sat.final_conflict(), None);
+    }
+
+    #[test]
+    fn reset() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        assert_eq!(sat.solve(), Some(true));
+        sat.reset();
+        sat.add_clause([-1]);
+        sat.add_clause([-2]);
+        assert_eq!(sat.solve(), Some(false));
+        assert_eq!(sat.cnf().len(), 3);
+    }
+
     #[test]
     fn bad_limit() {
         let mut sat = pigeon_hole(5);
         assert!(sat.set_limit("bad", 0) == Err(Error::new("unknown limit")));
     }
 
+    #[test]
+    fn values() {
+        let mut sat = Solver::new();
+        sat.add_clause([1]);
+        sat.add_clause([-2]);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.values(&[1, -1, 2, -2]), [Some(true), Some(false), Some(false), Some(true)]);
+    }
+
+    #[test]
+    fn model() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2, 3]);
+        sat.add_clause([-1, -2]);
+        sat.reserve(4);
+        assert_eq!(sat.num_vars(), 4);
+        assert_eq!(sat.cnf().len(), 2);
+        assert_eq!(sat.solve(), Some(true));
+        assert_eq!(sat.model().num_vars(), 4);
+        assert_eq!(sat.dont_care_count(), (1..=4).filter(|&var| sat.model().value(var).is_none()).count() as i32);
+        let model = sat.minimize_model();
+        assert_eq!(model.num_vars(), 4);
+        let trues: Vec<i32> = (1..=4).filter(|&var| model.value(var) == Some(true)).collect();
+        assert_eq!(trues.len(), 1);
+        assert!(trues[0] <= 3);
+    }
+
+    #[test]
+    fn count_models() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        assert_eq!(sat.count_models(10), 3);
+        assert_eq!(sat.count_models(2), 2);
+        sat.reserve(3);
+        assert_eq!(sat.count_models(10), 6);
+        sat.add_clause([-1]);
+        sat.add_clause([-2]);
+        assert_eq!(sat.count_models(10), 0);
+    }
+
+    #[test]
+    fn unsat_clause_core() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2]);
+        sat.add_clause([3]);
+        sat.add_clause([-1]);
+        assert!(sat.unsat_clause_core().is_empty());
+        sat.add_clause([-2]);
+        assert_eq!(sat.unsat_clause_core(), [0, 2, 3]);
+    }
+
+    #[test]
+    fn equivalent() {
+        let mut sat = Solver::new();
+        sat.add_clause([-1, 2]);
+        sat.add_clause([1, -2]);
+        assert_eq!(sat.solve(), Some(true));
+        assert!(sat.equivalent(1, 1));
+        assert!(!sat.equivalent(1, -1));
+    }
+
     #[test]
     fn moving() {
         let mut sat = pigeon_hole(5);
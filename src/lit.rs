@@ -0,0 +1,90 @@
+use std::fmt;
+use std::ops::Neg;
+
+/// A non-zero DIMACS literal. Unlike a bare `i32`, a `Lit` is always valid,
+/// and it converts to `i32` wherever clauses are accepted.
+/// # Examples
+/// ```
+/// use cat_solver::Lit;
+/// let x = Lit::new(1, true);
+/// let y = Lit::new(2, false);
+/// let mut sat = cat_solver::Solver::new();
+/// sat.add_clause([x, y]);
+/// sat.add_clause([-x]);
+/// assert_eq!(sat.solve(), Some(true));
+/// assert_eq!(sat.value(y.into()), Some(true));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lit(i32);
+
+impl Lit {
+    /// Returns the literal of variable `var` with the given polarity.
+    /// Panics unless `var` is positive.
+    #[inline]
+    pub fn new(var: i32, positive: bool) -> Self {
+        Lit(crate::lit(var, positive))
+    }
+
+    /// Returns the literal for the given DIMACS integer.
+    /// Panics if `lit` is zero or `i32::MIN`.
+    #[inline]
+    pub fn from_dimacs(lit: i32) -> Self {
+        assert!(crate::valid_lit(lit), "invalid literal {}", lit);
+        Lit(lit)
+    }
+
+    /// Returns the variable of the literal.
+    #[inline]
+    pub fn var(self) -> i32 {
+        self.0.abs()
+    }
+
+    /// Returns whether the literal is positive.
+    #[inline]
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+}
+
+impl Neg for Lit {
+    type Output = Lit;
+
+    #[inline]
+    fn neg(self) -> Lit {
+        Lit(-self.0)
+    }
+}
+
+impl From<Lit> for i32 {
+    #[inline]
+    fn from(lit: Lit) -> i32 {
+        lit.0
+    }
+}
+
+impl fmt::Display for Lit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lit() {
+        let x = Lit::new(3, false);
+        assert_eq!(i32::from(x), -3);
+        assert_eq!(x.var(), 3);
+        assert!(!x.is_positive());
+        assert_eq!(-x, Lit::from_dimacs(3));
+        assert_eq!((-x).to_string(), "3");
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero() {
+        Lit::from_dimacs(0);
+    }
+}
@@ -0,0 +1,78 @@
+/// An assignment of the variables `1..=num_vars()`, as read from a solver.
+/// Variables the solver left unassigned (don't-cares) have the value `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Model {
+    values: Vec<Option<bool>>,
+}
+
+impl Model {
+    /// Returns the value of the given literal. The result is `None` for
+    /// don't-care variables and for variables beyond `num_vars()`.
+    #[inline]
+    pub fn value(&self, lit: i32) -> Option<bool> {
+        debug_assert!(crate::valid_lit(lit));
+        let val = *self.values.get(lit.unsigned_abs() as usize - 1)?;
+        val.map(|val| val == (lit > 0))
+    }
+
+    /// Returns the number of variables covered by the model.
+    #[inline]
+    pub fn num_vars(&self) -> i32 {
+        self.values.len() as i32
+    }
+
+    /// Returns the variables, in increasing order, whose values differ
+    /// between the two models. A don't-care variable which the other model
+    /// assigns counts as a change, as do variables covered by only one of
+    /// the models and assigned there.
+    pub fn diff(&self, other: &Model) -> Vec<i32> {
+        self.diff_by(other, |a, b| a != b)
+    }
+
+    /// Like `diff`, but only reports variables assigned opposite values,
+    /// ignoring those which are don't-cares in either model.
+    pub fn diff_assigned(&self, other: &Model) -> Vec<i32> {
+        self.diff_by(other, |a, b| a.zip(b).is_some_and(|(a, b)| a != b))
+    }
+
+    fn diff_by<F>(&self, other: &Model, changed: F) -> Vec<i32>
+    where
+        F: Fn(Option<bool>, Option<bool>) -> bool,
+    {
+        let num_vars = self.num_vars().max(other.num_vars());
+        (1..=num_vars).filter(|&var| changed(self.value(var), other.value(var))).collect()
+    }
+}
+
+/// Builds a model from the values of the variables `1..=values.len()`.
+impl From<Vec<Option<bool>>> for Model {
+    fn from(values: Vec<Option<bool>>) -> Self {
+        Model { values }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value() {
+        let model = Model::from(vec![Some(true), None, Some(false)]);
+        assert_eq!(model.num_vars(), 3);
+        assert_eq!(model.value(1), Some(true));
+        assert_eq!(model.value(-1), Some(false));
+        assert_eq!(model.value(2), None);
+        assert_eq!(model.value(-3), Some(true));
+        assert_eq!(model.value(4), None);
+    }
+
+    #[test]
+    fn diff() {
+        let a = Model::from(vec![Some(true), None, Some(false), Some(true)]);
+        let b = Model::from(vec![Some(true), Some(false), Some(true), None, Some(false)]);
+        assert_eq!(a.diff(&b), [2, 3, 4, 5]);
+        assert_eq!(b.diff(&a), [2, 3, 4, 5]);
+        assert_eq!(a.diff_assigned(&b), [3]);
+        assert!(a.diff(&a).is_empty());
+    }
+}
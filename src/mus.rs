@@ -0,0 +1,75 @@
+use crate::{Cnf, Solver};
+
+/// Deletion-based extraction of a minimal unsatisfiable subset: each clause
+/// is dropped in turn, and kept out if the rest stays unsatisfiable. Returns
+/// the indices of the subset, or `None` if the clauses are not shown to be
+/// unsatisfiable. A solve ending undecided keeps its clause, so the subset
+/// may then not be minimal. Performs `clauses.len() + 1` solves, each on a
+/// fresh solver from `new_solver`.
+pub(crate) fn deletion_mus<F>(clauses: &[&[i32]], new_solver: F) -> Option<Vec<usize>>
+where
+    F: Fn() -> Solver,
+{
+    let solve = |indices: &mut dyn Iterator<Item = usize>| {
+        let mut sat = new_solver();
+        for i in indices {
+            sat.add_clause(clauses[i].iter().copied());
+        }
+        sat.solve()
+    };
+    let mut core: Vec<usize> = (0..clauses.len()).collect();
+    if solve(&mut core.iter().copied()) != Some(false) {
+        return None;
+    }
+    let mut i = 0;
+    while i < core.len() {
+        let without = core[..i].iter().chain(&core[i + 1..]).copied();
+        if solve(&mut { without }) == Some(false) {
+            core.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    Some(core)
+}
+
+impl Cnf {
+    /// Returns a minimal unsatisfiable subset of the clauses, in their
+    /// original order: dropping any one of them makes the rest satisfiable.
+    /// The subset is found by deletion, each clause being dropped in turn
+    /// and kept out if the rest stays unsatisfiable, which takes one solve
+    /// per clause on top of the initial one. The formula must be
+    /// unsatisfiable to start with; otherwise the empty formula is returned.
+    pub fn compute_mus(&self) -> Cnf {
+        let clauses: Vec<&[i32]> = self.clauses().collect();
+        let mut mus = Cnf::new();
+        for i in deletion_mus(&clauses, Solver::new).unwrap_or_default() {
+            mus.add_clause(clauses[i].iter().copied());
+        }
+        mus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mus() {
+        let clauses: [&[i32]; 5] = [&[1, 2], &[-1], &[3], &[-2], &[-3, 1]];
+        let core = deletion_mus(&clauses, Solver::new).unwrap();
+        assert_eq!(core, [0, 1, 3]);
+        assert_eq!(deletion_mus(&clauses[..3], Solver::new), None);
+    }
+
+    #[test]
+    fn compute_mus() {
+        let mut cnf = Cnf::new();
+        for clause in [&[1, 2][..], &[-1], &[3], &[-2], &[-3, 1]] {
+            cnf.add_clause(clause.iter().copied());
+        }
+        let mus = cnf.compute_mus();
+        assert_eq!(mus.clauses().collect::<Vec<_>>(), [&[1, 2][..], &[-1][..], &[-2][..]]);
+        assert_eq!(Cnf::new().compute_mus(), Cnf::new());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::Solver;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, PoisonError};
+
+/// A bounded pool of solvers, for servers answering many short queries.
+/// Solvers are handed out empty by `acquire` and cleared when they are
+/// returned. A Kissat instance cannot be cleared in place, so clearing a
+/// solver still allocates a fresh instance, but it happens on return rather
+/// than on `acquire`, and the wrapper's buffers are kept.
+pub struct SolverPool {
+    solvers: Mutex<Vec<Solver>>,
+    capacity: usize,
+}
+
+impl SolverPool {
+    /// Creates an empty pool which keeps at most `capacity` idle solvers.
+    pub fn new(capacity: usize) -> Self {
+        SolverPool {
+            solvers: Mutex::new(Vec::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// Takes an idle solver from the pool, or creates one if none is idle.
+    pub fn acquire(&self) -> PooledSolver<'_> {
+        let sat = self.lock().pop().unwrap_or_default();
+        PooledSolver { pool: self, sat: Some(sat) }
+    }
+
+    /// Returns the number of idle solvers in the pool.
+    pub fn idle(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Solver>> {
+        self.solvers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A solver borrowed from a `SolverPool`, which it is returned to on drop.
+pub struct PooledSolver<'a> {
+    pool: &'a SolverPool,
+    sat: Option<Solver>,
+}
+
+impl PooledSolver<'_> {
+    /// Takes the solver out of the pool for good.
+    pub fn into_inner(mut self) -> Solver {
+        self.sat.take().unwrap()
+    }
+}
+
+impl Deref for PooledSolver<'_> {
+    type Target = Solver;
+
+    fn deref(&self) -> &Solver {
+        self.sat.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledSolver<'_> {
+    fn deref_mut(&mut self) -> &mut Solver {
+        self.sat.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledSolver<'_> {
+    fn drop(&mut self) {
+        let Some(mut sat) = self.sat.take() else {
+            return;
+        };
+        if self.pool.idle() < self.pool.capacity {
+            sat.clear();
+            let mut solvers = self.pool.lock();
+            if solvers.len() < self.pool.capacity {
+                solvers.push(sat);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool() {
+        let pool = SolverPool::new(1);
+        let mut a = pool.acquire();
+        a.add_clause([1]);
+        a.add_clause([-1]);
+        assert_eq!(a.solve(), Some(false));
+        let b = pool.acquire();
+        drop(a);
+        drop(b);
+        assert_eq!(pool.idle(), 1);
+        let mut c = pool.acquire();
+        assert_eq!(pool.idle(), 0);
+        assert!(c.cnf().is_empty() && c.can_add_clauses());
+        c.add_clause([1]);
+        assert_eq!(c.solve(), Some(true));
+        let c = c.into_inner();
+        assert_eq!(c.cnf().len(), 1);
+        assert_eq!(pool.idle(), 0);
+    }
+
+    #[test]
+    fn send() {
+        fn check<T: Send + Sync>() {}
+        check::<SolverPool>();
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{Error, Limits, Solver};
+use std::fs;
+use std::path::Path;
+
+fn invalid(key: &str) -> Error {
+    Error::new(&format!("invalid value for profile key '{}'", key))
+}
+
+fn limit(key: &str, value: &toml::Value) -> Result<Option<u32>, Error> {
+    let limit = value.as_integer().and_then(|limit| u32::try_from(limit).ok());
+    limit.map(Some).ok_or_else(|| invalid(key))
+}
+
+impl Solver {
+    /// Applies a tuning profile read from a TOML file, such as:
+    /// ```toml
+    /// configuration = "unsat"
+    /// conflicts = 100000
+    ///
+    /// [options]
+    /// seed = 7
+    /// vivify = false
+    /// ```
+    /// The optional `configuration` key is applied by `set_config`; the
+    /// options, given as integers or booleans, by `set_options`; and the
+    /// `conflicts` and `decisions` keys set limits for the next solve, as
+    /// `set_limits` does. Other keys are an error unless the profile sets
+    /// `lenient = true`. The whole profile is checked first, so nothing is
+    /// applied if any part of it is rejected.
+    pub fn apply_profile(&mut self, path: &Path) -> Result<(), Error> {
+        let text = fs::read_to_string(path).map_err(|err| Error::new(&err.to_string()))?;
+        let profile: toml::Table = text.parse().map_err(|err: toml::de::Error| Error::new(&err.to_string()))?;
+        let lenient = match profile.get("lenient") {
+            Some(value) => value.as_bool().ok_or_else(|| invalid("lenient"))?,
+            None => false,
+        };
+        let mut config = None;
+        let mut options = Vec::new();
+        let mut limits = Limits::default();
+        for (key, value) in &profile {
+            match key.as_str() {
+                "lenient" => {}
+                "configuration" => config = Some(value.as_str().ok_or_else(|| invalid(key))?),
+                "conflicts" => limits.conflicts = limit(key, value)?,
+                "decisions" => limits.decisions = limit(key, value)?,
+                "options" => {
+                    for (name, value) in value.as_table().ok_or_else(|| invalid(key))? {
+                        let value = value.as_integer().or_else(|| value.as_bool().map(i64::from));
+                        let value = value.and_then(|value| i32::try_from(value).ok());
+                        options.push((name.as_str(), value.ok_or_else(|| invalid(name))?));
+                    }
+                }
+                _ if lenient => {}
+                _ => return Err(Error::new(&format!("unknown profile key '{}'", key))),
+            }
+        }
+        // check everything on a scratch solver, so that a rejected option
+        // does not leave the configuration applied
+        let mut scratch = Solver::new();
+        if let Some(config) = config {
+            scratch.set_config(config)?;
+        }
+        scratch.set_options(&options)?;
+        if let Some(config) = config {
+            self.set_config(config)?;
+        }
+        self.set_options(&options)?;
+        self.set_limits(&limits);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(sat: &mut Solver, profile: &str) -> Result<(), Error> {
+        let path = std::env::temp_dir().join(format!("cat_solver_profile_{}.toml", std::process::id()));
+        fs::write(&path, profile).unwrap();
+        let result = sat.apply_profile(&path);
+        fs::remove_file(&path).unwrap();
+        result
+    }
+
+    #[test]
+    fn apply_profile() {
+        let mut sat = crate::tests::pigeon_hole(6);
+        apply(&mut sat, "configuration = \"unsat\"\nconflicts = 10\n[options]\ntarget = 1\n").unwrap();
+        assert_eq!(sat.options.len(), 1);
+        assert_eq!(sat.solve(), None);
+        let unknown = "tuning = 3\n";
+        assert_eq!(apply(&mut sat, unknown), Err(Error::new("unknown profile key 'tuning'")));
+        assert!(apply(&mut sat, &format!("lenient = true\n{}", unknown)).is_ok());
+        assert_eq!(apply(&mut sat, "conflicts = -1\n"), Err(Error::new("invalid value for profile key 'conflicts'")));
+        assert!(apply(&mut sat, "[options]\ntarget = 9\n").is_err());
+        let mut sat = Solver::new();
+        assert!(apply(&mut sat, "configuration = \"sat\"\n[options]\ntarget = 9\n").is_err());
+        assert!(sat.config.is_none());
+    }
+}
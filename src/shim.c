@@ -0,0 +1,74 @@
+// Accessors for Kissat internals which are not part of its public API.
+
+#include "../kissat/src/internal.h"
+
+struct cat_solver_statistics {
+  uint64_t conflicts;
+  uint64_t decisions;
+  uint64_t propagations;
+  uint64_t restarts;
+};
+
+void cat_solver_statistics (kissat *solver,
+                            struct cat_solver_statistics *out) {
+  const statistics *statistics = &solver->statistics;
+  out->conflicts = statistics->conflicts;
+  out->decisions = statistics->decisions;
+  out->propagations = statistics->propagations;
+  out->restarts = statistics->restarts;
+}
+
+// Copies the current trail as external literals into `out`, provided it
+// holds at least as many literals as the trail, and returns the trail size.
+// Literals of variables without an external name are skipped and leave the
+// remaining entries of `out` untouched.
+size_t cat_solver_trail (kissat *solver, int *out, size_t capacity) {
+  const unsigned *begin = BEGIN_ARRAY (solver->trail);
+  const unsigned *end = END_ARRAY (solver->trail);
+  const size_t size = end - begin;
+  if (size > capacity)
+    return size;
+  size_t exported = 0;
+  for (const unsigned *p = begin; p != end; p++) {
+    const unsigned ilit = *p;
+    const int elit = PEEK_STACK (solver->export, IDX (ilit));
+    if (elit)
+      out[exported++] = NEGATED (ilit) ? -elit : elit;
+  }
+  return exported;
+}
+
+// Reports the sizes of the clause database. Binary clauses are counted as
+// irredundant. The counters are always present, since build.rs does not
+// define NSTATISTICS.
+void cat_solver_clauses (kissat *solver, uint64_t *irredundant,
+                         uint64_t *redundant) {
+  const statistics *statistics = &solver->statistics;
+  *irredundant = statistics->clauses_irredundant + statistics->clauses_binary;
+  *redundant = statistics->clauses_redundant;
+}
+
+// Whether `name` is one of Kissat's options. Unknown names read back as 0
+// from `kissat_get_option`, so they cannot be told apart by value alone.
+// Without options there is nothing to look up, and the wrapper only checks
+// that the value read back is the one given.
+int cat_solver_has_option (const char *name) {
+#ifdef NOPTIONS
+  (void) name;
+  return 0;
+#else
+  return kissat_options_has (name) != 0;
+#endif
+}
+
+// The largest variable index Kissat accepts in `kissat_add`.
+int cat_solver_max_var (void) { return EXTERNAL_MAX_VAR; }
+
+// Whether clause references are 32-bit words, as with `./configure --compact`.
+int cat_solver_compact (void) {
+#ifdef COMPACT
+  return 1;
+#else
+  return 0;
+#endif
+}
@@ -0,0 +1,32 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_sigint(_: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::Relaxed);
+}
+
+/// Returns whether SIGINT arrived since the handler was installed.
+pub(crate) fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+}
+
+/// Installs the SIGINT handler, and restores the previous one when dropped.
+pub(crate) struct Guard {
+    previous: libc::sighandler_t,
+}
+
+impl Guard {
+    pub(crate) fn install() -> Self {
+        INTERRUPTED.store(false, Ordering::Relaxed);
+        let handler = on_sigint as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        let previous = unsafe { libc::signal(libc::SIGINT, handler) };
+        Guard { previous }
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        unsafe { libc::signal(libc::SIGINT, self.previous) };
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{Model, Solver};
+
+/// An iterator over the models of a formula, created by
+/// `Solver::iter_solutions`.
+/// # Examples
+/// ```
+/// let mut sat = cat_solver::Solver::new();
+/// sat.add_clause([1, 2]);
+/// let mut models: Vec<_> = sat.iter_solutions().map(|model| (model.value(1), model.value(2))).collect();
+/// models.sort();
+/// assert_eq!(models, [(Some(false), Some(true)), (Some(true), Some(false)), (Some(true), Some(true))]);
+/// ```
+pub struct SolutionIter {
+    sat: Solver,
+    num_vars: i32,
+    /// The result of the solve which ended the iteration, if it has ended.
+    end: Option<Option<bool>>,
+}
+
+impl SolutionIter {
+    /// Returns whether the iteration ended because all models were found,
+    /// rather than because a solve was undecided. This is `false` while
+    /// models remain to be found.
+    pub fn is_complete(&self) -> bool {
+        self.end == Some(Some(false))
+    }
+}
+
+impl Iterator for SolutionIter {
+    type Item = Model;
+
+    fn next(&mut self) -> Option<Model> {
+        if self.end.is_some() {
+            return None;
+        }
+        let result = self.sat.solve();
+        if result != Some(true) {
+            self.end = Some(result);
+            return None;
+        }
+        let model = self.sat.model();
+        self.sat.reset();
+        self.sat.add_clause((1..=self.num_vars).map(|var| if model.value(var) == Some(true) { -var } else { var }));
+        Some(model)
+    }
+}
+
+impl Solver {
+    /// Lazily enumerates the models of the formula over the variables
+    /// `1..=num_vars()`, blocking each one as it is returned, until the
+    /// formula becomes unsatisfiable or a solve is undecided. Kissat is not
+    /// incremental, so the iterator works on its own copy of the recorded
+    /// clauses and configuration, and each model costs a fresh solve of the
+    /// formula with all previous models blocked, as in `count_models`.
+    /// Assumptions and limits do not apply, and this solver is left
+    /// untouched.
+    pub fn iter_solutions(&self) -> SolutionIter {
+        SolutionIter {
+            sat: self.reload(),
+            num_vars: self.max_var,
+            end: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter_solutions() {
+        let mut sat = Solver::new();
+        sat.add_clause([1, 2, 3]);
+        let mut solutions = sat.iter_solutions();
+        assert_eq!(solutions.by_ref().take(3).count(), 3);
+        assert!(!solutions.is_complete());
+        assert_eq!(solutions.by_ref().count(), 4);
+        assert!(solutions.is_complete());
+        assert_eq!(solutions.next(), None);
+        assert_eq!(sat.cnf().len(), 1);
+        assert_eq!(sat.iter_solutions().filter(|model| model.value(1) == Some(false)).count(), 3);
+        assert_eq!(crate::tests::pigeon_hole(2).iter_solutions().count(), 0);
+    }
+}
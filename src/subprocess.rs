@@ -0,0 +1,84 @@
+use crate::{Cnf, Limits, Model, Outcome};
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, BufWriter, Read, Write};
+use std::process::{Command, Stdio};
+
+/// Environment variable naming the helper program run by `solve_isolated`.
+const HELPER_VAR: &str = "CAT_SOLVER_HELPER";
+/// Default helper program, looked up in `PATH`.
+const HELPER: &str = "cat_solver_helper";
+
+/// Solves the formula in a child process, so that an abort inside Kissat
+/// only kills the child. The child runs the `cat_solver_helper` binary
+/// built with the `subprocess` feature, which is looked up in `PATH`
+/// unless the `CAT_SOLVER_HELPER` environment variable gives its path.
+/// The formula is piped to it in DIMACS format, and the model is parsed
+/// from its output. A child which dies or fails is reported as an error.
+pub fn solve_isolated(cnf: &Cnf, limits: &Limits) -> io::Result<Outcome> {
+    let helper = env::var_os(HELPER_VAR).unwrap_or_else(|| OsString::from(HELPER));
+    let mut command = Command::new(helper);
+    if let Some(conflicts) = limits.conflicts {
+        command.arg(format!("--conflicts={}", conflicts));
+    }
+    if let Some(decisions) = limits.decisions {
+        command.arg(format!("--decisions={}", decisions));
+    }
+    let mut child = command.stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+
+    // The helper reads the whole formula before it writes anything.
+    let mut stdin = BufWriter::new(child.stdin.take().expect("piped stdin"));
+    let written = cnf.write_dimacs(&mut stdin).and_then(|_| stdin.flush());
+    drop(stdin);
+    let mut output = String::new();
+    child.stdout.take().expect("piped stdout").read_to_string(&mut output)?;
+    let status = child.wait()?;
+    match status.code() {
+        Some(0) | Some(10) | Some(20) => written?,
+        _ => return Err(io::Error::other(format!("solver helper failed: {}", status))),
+    }
+    parse_output(&output, cnf.num_vars())
+}
+
+/// Parses the SAT competition output format written by the helper.
+fn parse_output(output: &str, num_vars: i32) -> io::Result<Outcome> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, format!("solver helper output: {}", msg));
+    let mut status = None;
+    let mut values = vec![None; num_vars as usize];
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("s ") {
+            status = Some(rest.trim());
+        } else if let Some(rest) = line.strip_prefix("v ") {
+            for token in rest.split_ascii_whitespace() {
+                let lit: i32 = token.parse().map_err(|_| invalid("invalid literal"))?;
+                if lit == 0 {
+                    continue;
+                }
+                let val = values.get_mut(lit.unsigned_abs() as usize - 1).ok_or_else(|| invalid("literal out of range"))?;
+                *val = Some(lit > 0);
+            }
+        }
+    }
+    match status {
+        Some("SATISFIABLE") => Ok(Outcome::Sat(Model::from(values))),
+        Some("UNSATISFIABLE") => Ok(Outcome::Unsat),
+        Some("UNKNOWN") => Ok(Outcome::Unknown),
+        _ => Err(invalid("missing status")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output() {
+        let output = "s SATISFIABLE\nv 1 -3 0\n";
+        let model = Model::from(vec![Some(true), None, Some(false)]);
+        assert_eq!(parse_output(output, 3).unwrap(), Outcome::Sat(model));
+        assert_eq!(parse_output("s UNSATISFIABLE\n", 3).unwrap(), Outcome::Unsat);
+        assert_eq!(parse_output("s UNKNOWN\n", 3).unwrap(), Outcome::Unknown);
+        assert!(parse_output("", 3).is_err());
+        assert!(parse_output("s SATISFIABLE\nv 4 0\n", 3).is_err());
+    }
+}